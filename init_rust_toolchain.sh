@@ -1,16 +1,41 @@
-AETHEROS_UNKNOWN_ELF_PATH=$(rustc --print sysroot)/lib/rustlib/x86_64-unknown-aether
-mkdir -p ${AETHEROS_UNKNOWN_ELF_PATH}/lib
-cp -r target-x86_64.json ${AETHEROS_UNKNOWN_ELF_PATH}/target.json
+# x86_64-unknown-aether is the userspace target. The softfloat variant keeps
+# SSE and the red zone off for kernel-mode crates, and aborts on panic.
+#
+# The userspace sysroot includes the profiler runtime, so that programs built
+# with `-Cinstrument-coverage` link. It is compiled from the compiler-rt
+# sources in RUST_COMPILER_RT_FOR_PROFILER, or taken prebuilt from
+# LLVM_PROFILER_RT_LIB.
+#
+# Set AETHER_SANITIZE=1 to instrument the softfloat sysroot with KCFI and
+# KASAN, for kernels built with `-Zsanitizer=kcfi -Zsanitizer=kernel-address`.
+build_sysroot() {
+    local target=$1
+    local spec=$2
+    local rustflags=$3
+    local features=$4
+    local target_path=$(rustc --print sysroot)/lib/rustlib/${target}
+    mkdir -p ${target_path}/lib
+    cp -r ${spec} ${target_path}/target.json
 
-CARGO_PROFILE_RELEASE_DEBUG=0 \
-    CARGO_PROFILE_RELEASE_DEBUG_ASSERTIONS=true \
-    RUSTC_BOOTSTRAP=1 \
-    RUSTFLAGS="-Cpanic=abort -Cforce-unwind-tables=yes -Cembed-bitcode=yes -Zforce-unstable-if-unmarked" \
-    __CARGO_DEFAULT_LIB_METADATA="stablestd" \
-    cargo build \
-    --target x86_64-unknown-aether \
-    -Zbinary-dep-depinfo \
-    --manifest-path "library/sysroot/Cargo.toml"
+    CARGO_PROFILE_RELEASE_DEBUG=0 \
+        CARGO_PROFILE_RELEASE_DEBUG_ASSERTIONS=true \
+        RUSTC_BOOTSTRAP=1 \
+        RUSTFLAGS="-Cforce-unwind-tables=yes -Cembed-bitcode=yes -Zforce-unstable-if-unmarked ${rustflags}" \
+        __CARGO_DEFAULT_LIB_METADATA="stablestd" \
+        cargo build \
+        --target ${target} \
+        -Zbinary-dep-depinfo \
+        --features backtrace${features:+,${features}} \
+        --manifest-path "library/sysroot/Cargo.toml"
 
-rm -rf ${AETHEROS_UNKNOWN_ELF_PATH}/lib/*.rlib
-cp library/target/x86_64-unknown-aether/debug/deps/*.rlib ${AETHEROS_UNKNOWN_ELF_PATH}/lib
+    rm -rf ${target_path}/lib/*.rlib
+    cp library/target/${target}/debug/deps/*.rlib ${target_path}/lib
+}
+
+build_sysroot x86_64-unknown-aether target-x86_64-user.json "" panic-unwind,profiler
+if [ -n "${AETHER_SANITIZE}" ]; then
+    build_sysroot x86_64-unknown-aether-softfloat target-x86_64.json \
+        "-Zsanitizer=kcfi -Zsanitizer=kernel-address" sanitizers
+else
+    build_sysroot x86_64-unknown-aether-softfloat target-x86_64.json
+fi
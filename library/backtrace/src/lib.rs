@@ -90,6 +90,7 @@
     all(feature = "std", target_env = "sgx", target_vendor = "fortanix"),
     feature(sgx_platform)
 )]
+#![cfg_attr(target_os = "aether", feature(linkage))]
 #![warn(rust_2018_idioms)]
 // When we're building as part of libstd, silence all warnings since they're
 // irrelevant as this crate is developed out-of-tree.
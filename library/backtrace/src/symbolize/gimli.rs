@@ -33,6 +33,7 @@ cfg_if::cfg_if! {
         #[path = "gimli/mmap_unix.rs"]
         mod mmap;
     } else if #[cfg(any(
+        target_os = "aether",
         target_os = "android",
         target_os = "freebsd",
         target_os = "fuchsia",
@@ -221,6 +222,7 @@ cfg_if::cfg_if! {
     } else if #[cfg(all(
         any(
             target_os = "linux",
+            target_os = "aether",
             target_os = "fuchsia",
             target_os = "freebsd",
             target_os = "hurd",
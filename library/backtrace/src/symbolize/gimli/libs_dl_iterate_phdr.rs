@@ -16,6 +16,13 @@ struct CallbackData {
     maps: Option<Vec<parse_running_mmaps::MapsEntry>>,
 }
 pub(super) fn native_libraries() -> Vec<Library> {
+    #[cfg(target_os = "aether")]
+    let Some(dl_iterate_phdr) = aether::dl_iterate_phdr() else {
+        return aether::native_libraries_from_auxv();
+    };
+    #[cfg(not(target_os = "aether"))]
+    use libc::dl_iterate_phdr;
+
     let mut cb_data = CallbackData {
         libs: Vec::new(),
         #[cfg(not(target_os = "hurd"))]
@@ -24,7 +31,7 @@ pub(super) fn native_libraries() -> Vec<Library> {
         maps: None,
     };
     unsafe {
-        libc::dl_iterate_phdr(Some(callback), core::ptr::addr_of_mut!(cb_data).cast());
+        dl_iterate_phdr(Some(callback), core::ptr::addr_of_mut!(cb_data).cast());
     }
     cb_data.libs
 }
@@ -120,3 +127,118 @@ unsafe extern "C" fn callback(
     });
     0
 }
+
+/// aether programs link libc statically, and builds of its libc that leave
+/// out `dl_iterate_phdr` are still in use. The executable is then the only
+/// object in the address space, and the kernel passes its program headers in
+/// the auxiliary vector, which is all `dl_iterate_phdr` would report anyway.
+///
+/// Linux passes the same auxiliary vector entries, so the fallback is also
+/// tested there.
+#[cfg(any(target_os = "aether", all(test, target_os = "linux")))]
+mod aether {
+    use super::super::mystd::env;
+    use super::{Library, LibrarySegment};
+    use alloc::vec::Vec;
+    use core::slice;
+
+    #[cfg(target_os = "aether")]
+    type DlIteratePhdr = unsafe extern "C" fn(
+        Option<
+            unsafe extern "C" fn(
+                *mut libc::dl_phdr_info,
+                libc::size_t,
+                *mut libc::c_void,
+            ) -> libc::c_int,
+        >,
+        *mut libc::c_void,
+    ) -> libc::c_int;
+
+    #[cfg(target_os = "aether")]
+    mod weak {
+        unsafe extern "C" {
+            #[linkage = "extern_weak"]
+            pub(super) static dl_iterate_phdr: Option<super::DlIteratePhdr>;
+        }
+    }
+
+    #[cfg(target_os = "aether")]
+    pub(super) fn dl_iterate_phdr() -> Option<DlIteratePhdr> {
+        // SAFETY: an undefined weak symbol reads as null, i.e. `None`.
+        unsafe { weak::dl_iterate_phdr }
+    }
+
+    pub(super) fn native_libraries_from_auxv() -> Vec<Library> {
+        // SAFETY: `getauxval` has no preconditions.
+        let phdr = unsafe { libc::getauxval(libc::AT_PHDR) } as *const libc::Elf64_Phdr;
+        let phnum = unsafe { libc::getauxval(libc::AT_PHNUM) } as usize;
+        if phdr.is_null() || phnum == 0 {
+            return Vec::new();
+        }
+        // SAFETY: the program headers stay mapped for the life of the process.
+        let headers = unsafe { slice::from_raw_parts(phdr, phnum) };
+        alloc::vec![Library {
+            name: env::current_exe().unwrap_or_default().into_os_string(),
+            segments: headers
+                .iter()
+                .map(|header| LibrarySegment {
+                    len: header.p_memsz as usize,
+                    stated_virtual_memory_address: header.p_vaddr as usize,
+                })
+                .collect(),
+            bias: bias(phdr as usize, headers),
+        }]
+    }
+
+    /// Computes the load bias of an executable whose program headers,
+    /// `headers`, were found at address `phdr`.
+    fn bias(phdr: usize, headers: &[libc::Elf64_Phdr]) -> usize {
+        // A no-PIE executable runs at its link address. Otherwise `PT_PHDR`
+        // tells where the headers were linked, and the difference from where
+        // they were loaded is the bias.
+        headers
+            .iter()
+            .find(|header| header.p_type == libc::PT_PHDR)
+            .map_or(0, |header| phdr.wrapping_sub(header.p_vaddr as usize))
+    }
+
+    #[test]
+    fn bias_from_pt_phdr() {
+        fn header(p_type: u32, p_vaddr: u64) -> libc::Elf64_Phdr {
+            libc::Elf64_Phdr {
+                p_type,
+                p_flags: 0,
+                p_offset: 0,
+                p_vaddr,
+                p_paddr: p_vaddr,
+                p_filesz: 0,
+                p_memsz: 0,
+                p_align: 0,
+            }
+        }
+
+        let pie = [header(libc::PT_PHDR, 0x40), header(libc::PT_LOAD, 0)];
+        assert_eq!(bias(0x5555_5555_4040, &pie), 0x5555_5555_4000);
+        // Without `PT_PHDR` the executable is assumed to run where it was linked.
+        let no_pie = [header(libc::PT_LOAD, 0x40_0000)];
+        assert_eq!(bias(0x40_0040, &no_pie), 0);
+    }
+
+    #[test]
+    fn auxv_matches_dl_iterate_phdr() {
+        let from_auxv = native_libraries_from_auxv();
+        assert_eq!(from_auxv.len(), 1);
+        // `dl_iterate_phdr` reports the executable first.
+        let from_dl = super::native_libraries();
+        let (auxv, dl) = (&from_auxv[0], &from_dl[0]);
+        assert_eq!(auxv.bias, dl.bias);
+        assert_eq!(auxv.segments.len(), dl.segments.len());
+        for (a, d) in auxv.segments.iter().zip(&dl.segments) {
+            assert_eq!(a.len, d.len);
+            assert_eq!(
+                a.stated_virtual_memory_address,
+                d.stated_virtual_memory_address
+            );
+        }
+    }
+}
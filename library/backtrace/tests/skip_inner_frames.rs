@@ -7,7 +7,7 @@ use core::ffi::c_void;
 const ENABLED: bool = cfg!(all(
     // Windows hasn't really been tested, and macOS doesn't support actually
     // finding an enclosing frame, so disable this
-    target_os = "linux",
+    any(target_os = "linux", target_os = "aether"),
     // On ARM finding the enclosing function is simply returning the ip itself.
     not(target_arch = "arm"),
 ));
@@ -5,7 +5,32 @@
 //! - <https://github.com/rust-lang/compiler-builtins/blob/master/build.rs>.
 
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+pub(crate) const PROFILE_SOURCES: &[&str] = &[
+    // tidy-alphabetical-start
+    "GCDAProfiling.c",
+    "InstrProfiling.c",
+    "InstrProfilingBuffer.c",
+    "InstrProfilingFile.c",
+    "InstrProfilingInternal.c",
+    "InstrProfilingMerge.c",
+    "InstrProfilingMergeFile.c",
+    "InstrProfilingNameVar.c",
+    "InstrProfilingPlatformAIX.c",
+    "InstrProfilingPlatformDarwin.c",
+    "InstrProfilingPlatformFuchsia.c",
+    "InstrProfilingPlatformLinux.c",
+    "InstrProfilingPlatformOther.c",
+    "InstrProfilingPlatformWindows.c",
+    "InstrProfilingRuntime.cpp",
+    "InstrProfilingUtil.c",
+    "InstrProfilingValue.c",
+    "InstrProfilingVersionVar.c",
+    "InstrProfilingWriter.c",
+    "WindowsMMap.c",
+    // tidy-alphabetical-end
+];
 
 fn main() {
     if let Ok(rt) = tracked_env_var("LLVM_PROFILER_RT_LIB") {
@@ -24,33 +49,53 @@ fn main() {
 
     let target_os = env::var("CARGO_CFG_TARGET_OS").expect("CARGO_CFG_TARGET_OS was not set");
     let target_env = env::var("CARGO_CFG_TARGET_ENV").expect("CARGO_CFG_TARGET_ENV was not set");
+    let unix = env::var_os("CARGO_CFG_UNIX").is_some();
+    // This should be a pretty good heuristic for when to set
+    // COMPILER_RT_HAS_ATOMICS
+    let atomics = env::var_os("CARGO_CFG_TARGET_HAS_ATOMIC")
+        .map(|features| features.to_string_lossy().to_lowercase().contains("ptr"))
+        .unwrap_or(false);
     let cfg = &mut cc::Build::new();
+    configure(cfg, &target_os, &target_env, unix, atomics);
 
-    let profile_sources = vec![
-        // tidy-alphabetical-start
-        "GCDAProfiling.c",
-        "InstrProfiling.c",
-        "InstrProfilingBuffer.c",
-        "InstrProfilingFile.c",
-        "InstrProfilingInternal.c",
-        "InstrProfilingMerge.c",
-        "InstrProfilingMergeFile.c",
-        "InstrProfilingNameVar.c",
-        "InstrProfilingPlatformAIX.c",
-        "InstrProfilingPlatformDarwin.c",
-        "InstrProfilingPlatformFuchsia.c",
-        "InstrProfilingPlatformLinux.c",
-        "InstrProfilingPlatformOther.c",
-        "InstrProfilingPlatformWindows.c",
-        "InstrProfilingRuntime.cpp",
-        "InstrProfilingUtil.c",
-        "InstrProfilingValue.c",
-        "InstrProfilingVersionVar.c",
-        "InstrProfilingWriter.c",
-        "WindowsMMap.c",
-        // tidy-alphabetical-end
-    ];
+    if target_os == "aether" {
+        let libc = tracked_env_var("AETHER_LIBC_DIR").unwrap_or_else(|_| "/usr/libc-x86_64".into());
+        configure_aether(cfg, Some(Path::new(&libc)));
+    }
+
+    // Get the LLVM `compiler-rt` directory from bootstrap.
+    let root = PathBuf::from(tracked_env_var_or_fallback(
+        "RUST_COMPILER_RT_FOR_PROFILER",
+        "../../src/llvm-project/compiler-rt",
+    ));
 
+    let src_root = root.join("lib").join("profile");
+    assert!(
+        src_root.exists(),
+        "profiler runtime source directory not found: {src_root:?}"
+    );
+    println!("cargo::rerun-if-changed={}", src_root.display());
+    for file in PROFILE_SOURCES {
+        cfg.file(src_root.join(file));
+    }
+
+    let include = root.join("include");
+    println!("cargo::rerun-if-changed={}", include.display());
+    cfg.include(include);
+
+    cfg.warnings(false);
+    cfg.compile("profiler-rt");
+}
+
+/// Applies the flags and defines compiler-rt's own build system uses for a
+/// target with the given properties.
+pub(crate) fn configure(
+    cfg: &mut cc::Build,
+    target_os: &str,
+    target_env: &str,
+    unix: bool,
+    atomics: bool,
+) {
     if target_env == "msvc" {
         // Don't pull in extra libraries on MSVC
         cfg.flag("/Zl");
@@ -72,41 +117,34 @@ fn main() {
     }
 
     // Assume that the Unixes we are building this for have fnctl() available
-    if env::var_os("CARGO_CFG_UNIX").is_some() {
+    if unix {
         cfg.define("COMPILER_RT_HAS_FCNTL_LCK", Some("1"));
     }
 
-    // This should be a pretty good heuristic for when to set
-    // COMPILER_RT_HAS_ATOMICS
-    if env::var_os("CARGO_CFG_TARGET_HAS_ATOMIC")
-        .map(|features| features.to_string_lossy().to_lowercase().contains("ptr"))
-        .unwrap_or(false)
-    {
+    if atomics {
         cfg.define("COMPILER_RT_HAS_ATOMICS", Some("1"));
     }
+}
 
-    // Get the LLVM `compiler-rt` directory from bootstrap.
-    let root = PathBuf::from(tracked_env_var_or_fallback(
-        "RUST_COMPILER_RT_FOR_PROFILER",
-        "../../src/llvm-project/compiler-rt",
-    ));
-
-    let src_root = root.join("lib").join("profile");
-    assert!(
-        src_root.exists(),
-        "profiler runtime source directory not found: {src_root:?}"
-    );
-    println!("cargo::rerun-if-changed={}", src_root.display());
-    for file in profile_sources {
-        cfg.file(src_root.join(file));
+/// Configures the runtime for aether, whose programs link statically against
+/// a musl-derived libc.
+///
+/// The aether target specs use the `x86_64-unknown-none` LLVM target, for
+/// which instrumented code registers its profile data at startup instead of
+/// relying on linker-defined section bounds. That is what
+/// `InstrProfilingPlatformOther.c` implements, but aether is built with a
+/// Linux-hosted GCC whose `__linux__` would select the Linux platform file.
+///
+/// `libc` is the libc installation to compile against, or `None` for the C
+/// compiler's own headers, which the host test in `tests/` relies on.
+pub(crate) fn configure_aether(cfg: &mut cc::Build, libc: Option<&Path>) {
+    for macro_name in ["__linux__", "__linux", "__gnu_linux__", "linux"] {
+        cfg.flag(&format!("-U{macro_name}"));
+    }
+    if let Some(libc) = libc {
+        cfg.flag("-nostdinc");
+        cfg.include(libc.join("include"));
     }
-
-    let include = root.join("include");
-    println!("cargo::rerun-if-changed={}", include.display());
-    cfg.include(include);
-
-    cfg.warnings(false);
-    cfg.compile("profiler-rt");
 }
 
 fn tracked_env_var(key: &str) -> Result<String, env::VarError> {
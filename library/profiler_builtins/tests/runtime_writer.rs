@@ -0,0 +1,107 @@
+//! Builds the profile runtime with the settings `build.rs` uses for aether,
+//! but against the host libc, and checks that a program linked with it writes
+//! its `.profraw` file where `LLVM_PROFILE_FILE` asks.
+
+#![cfg(all(target_os = "linux", target_arch = "x86_64"))]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::{env, fs};
+
+#[allow(dead_code)]
+#[path = "../build.rs"]
+mod build;
+
+const HOST: &str = "x86_64-unknown-linux-gnu";
+
+/// The first word of a raw profile, `\xfflprofr\x81`.
+const RAW_PROFILE_MAGIC: u64 = 0xff6c_7072_6f66_7281;
+
+/// Stands in for an instrumented program. Referencing the runtime hook is what
+/// pulls the runtime, and with it the `atexit` writer, into the link.
+const DRIVER: &str = "
+extern int __llvm_profile_runtime;
+int main(void) { return __llvm_profile_runtime; }
+";
+
+/// Finds the compiler-rt sources the same way `build.rs` does. The crate can
+/// only be built without them from a prebuilt `LLVM_PROFILER_RT_LIB`, which
+/// this test can't use, so their absence is an error rather than a skip.
+fn compiler_rt() -> PathBuf {
+    let root = env::var_os("RUST_COMPILER_RT_FOR_PROFILER")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../../src/llvm-project/compiler-rt")
+        });
+    assert!(
+        root.join("lib").join("profile").exists(),
+        "compiler-rt sources not found at {root:?}; set RUST_COMPILER_RT_FOR_PROFILER"
+    );
+    root
+}
+
+fn build_driver(root: &Path, out: &Path) -> PathBuf {
+    let cfg = &mut cc::Build::new();
+    cfg.target(HOST)
+        .host(HOST)
+        .opt_level(2)
+        .out_dir(out)
+        .cargo_metadata(false)
+        .warnings(false);
+    build::configure(cfg, "aether", "musl", true, true);
+    build::configure_aether(cfg, None);
+    for file in build::PROFILE_SOURCES {
+        cfg.file(root.join("lib").join("profile").join(file));
+    }
+    cfg.include(root.join("include"));
+    cfg.compile("profiler-rt");
+
+    let driver = out.join("driver.c");
+    fs::write(&driver, DRIVER).unwrap();
+    let exe = out.join("driver");
+    let status = cfg
+        .get_compiler()
+        .to_command()
+        .arg(&driver)
+        .arg(out.join("libprofiler-rt.a"))
+        .arg("-o")
+        .arg(&exe)
+        .status()
+        .unwrap();
+    assert!(status.success(), "linking the driver failed");
+    exe
+}
+
+#[test]
+fn writes_profile_to_pattern() {
+    let root = compiler_rt();
+    let out = env::temp_dir().join(format!("profiler-rt-aether-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&out);
+    fs::create_dir_all(&out).unwrap();
+    let exe = build_driver(&root, &out);
+
+    let profiles = out.join("profiles");
+    fs::create_dir(&profiles).unwrap();
+    let mut child = Command::new(&exe)
+        .env("LLVM_PROFILE_FILE", profiles.join("%p-%m.profraw"))
+        .spawn()
+        .unwrap();
+    let pid = child.id();
+    assert!(child.wait().unwrap().success());
+
+    let entries: Vec<_> = fs::read_dir(&profiles)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .collect();
+    assert_eq!(entries.len(), 1, "{entries:?}");
+    let name = entries[0].file_name().unwrap().to_str().unwrap();
+    // `%m` expands to a signature of the binary followed by a pool index.
+    assert!(name.starts_with(&format!("{pid}-")), "{name}");
+    assert!(name.ends_with(".profraw"), "{name}");
+
+    let data = fs::read(&entries[0]).unwrap();
+    let magic = u64::from_le_bytes(data[..8].try_into().unwrap());
+    assert_eq!(magic, RAW_PROFILE_MAGIC);
+
+    fs::remove_dir_all(&out).unwrap();
+}
@@ -64,7 +64,7 @@ where
     W: Write,
 {
     cfg_if::cfg_if! {
-        if #[cfg(any(target_os = "linux", target_os = "android"))] {
+        if #[cfg(any(target_os = "linux", target_os = "android", target_os = "aether"))] {
             crate::sys::kernel_copy::copy_spec(reader, writer)
         } else {
             generic_copy(reader, writer)
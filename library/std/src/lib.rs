@@ -288,6 +288,7 @@
 #![feature(allow_internal_unstable)]
 #![feature(asm_experimental_arch)]
 #![feature(autodiff)]
+#![feature(cfg_sanitize)]
 #![feature(cfg_sanitizer_cfi)]
 #![feature(cfg_target_thread_local)]
 #![feature(cfi_encoding)]
@@ -478,6 +479,16 @@ extern crate miniz_oxide;
 #[cfg(test)]
 extern crate std as realstd;
 
+// The `sanitizers` feature only promises instrumented rlibs; the
+// instrumentation itself comes from the flags std is compiled with.
+#[cfg(all(
+    feature = "sanitizers",
+    not(any(sanitize = "kcfi", sanitize = "kernel-address"))
+))]
+core::compile_error!(
+    "the `sanitizers` feature requires building with `-Zsanitizer=kcfi` or `-Zsanitizer=kernel-address`"
+);
+
 // The standard macros that are not built-in to the compiler.
 #[macro_use]
 mod macros;
@@ -600,7 +611,7 @@ pub mod f64;
 #[macro_use]
 pub mod thread;
 pub mod ascii;
-// pub mod backtrace;
+pub mod backtrace;
 #[unstable(feature = "bstr", issue = "134915")]
 pub mod bstr;
 pub mod collections;
@@ -701,14 +712,14 @@ pub mod alloc;
 // Private support modules
 mod panicking;
 
-// #[path = "../../backtrace/src/lib.rs"]
-// #[allow(
-//     dead_code,
-//     unused_attributes,
-//     fuzzy_provenance_casts,
-//     unsafe_op_in_unsafe_fn
-// )]
-// mod backtrace_rs;
+#[path = "../../backtrace/src/lib.rs"]
+#[allow(
+    dead_code,
+    unused_attributes,
+    fuzzy_provenance_casts,
+    unsafe_op_in_unsafe_fn
+)]
+mod backtrace_rs;
 
 #[unstable(feature = "cfg_match", issue = "115585")]
 pub use core::cfg_match;
@@ -322,6 +322,54 @@ pub trait MetadataExt {
     /// ```
     #[stable(feature = "metadata_ext2", since = "1.8.0")]
     fn st_blocks(&self) -> u64;
+    /// Returns the creation time of the file, in seconds since the Unix
+    /// epoch. Filesystems that don't record it report 0.
+    ///
+    /// [`fs::Metadata::created`] is the supported way to read the creation
+    /// time; it reports an error where this returns 0.
+    ///
+    /// [`fs::Metadata::created`]: crate::fs::Metadata::created
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(aether_birthtime)]
+    /// use std::fs;
+    /// use std::io;
+    /// use std::os::aether::fs::MetadataExt;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let meta = fs::metadata("some_file")?;
+    ///     println!("{}", meta.st_birthtime());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "aether_birthtime", issue = "none")]
+    #[allow(deprecated)]
+    fn st_birthtime(&self) -> i64 {
+        self.as_raw_stat().st_birthtime as i64
+    }
+    /// Returns the nanosecond part of the creation time of the file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(aether_birthtime)]
+    /// use std::fs;
+    /// use std::io;
+    /// use std::os::aether::fs::MetadataExt;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let meta = fs::metadata("some_file")?;
+    ///     println!("{}", meta.st_birthtime_nsec());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "aether_birthtime", issue = "none")]
+    #[allow(deprecated)]
+    fn st_birthtime_nsec(&self) -> i64 {
+        self.as_raw_stat().st_birthtime_nsec as i64
+    }
 }
 
 #[stable(feature = "metadata_ext", since = "1.1.0")]
@@ -378,4 +426,10 @@ impl MetadataExt for Metadata {
     fn st_blocks(&self) -> u64 {
         self.as_inner().as_inner().st_blocks as u64
     }
+    fn st_birthtime(&self) -> i64 {
+        self.as_inner().as_inner().st_birthtime as i64
+    }
+    fn st_birthtime_nsec(&self) -> i64 {
+        self.as_inner().as_inner().st_birthtime_nsec as i64
+    }
 }
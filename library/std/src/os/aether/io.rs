@@ -0,0 +1,13 @@
+//! aether-specific extensions to general I/O primitives.
+//!
+//! Every kernel object aether hands to userspace, including the process
+//! handles returned by [`ChildExt::duplicate_handle`], is a file descriptor,
+//! so the types from [`std::os::fd`] are the aether handle types.
+//!
+//! [`ChildExt::duplicate_handle`]: crate::os::aether::process::ChildExt::duplicate_handle
+//! [`std::os::fd`]: crate::os::fd
+
+#![unstable(feature = "aether_ext", issue = "none")]
+
+#[unstable(feature = "aether_ext", issue = "none")]
+pub use crate::os::fd::*;
@@ -1,6 +1,10 @@
-//! Redox-specific definitions
+//! aether-specific definitions
 
 #![stable(feature = "raw_ext", since = "1.1.0")]
 
 pub mod fs;
+pub mod io;
+pub mod net;
+pub mod process;
 pub mod raw;
+pub mod thread;
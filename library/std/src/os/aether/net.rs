@@ -0,0 +1,6 @@
+//! aether-specific networking functionality.
+
+#![unstable(feature = "aether_ext", issue = "none")]
+
+#[unstable(feature = "aether_ext", issue = "none")]
+pub use crate::os::net::linux_ext::addr::SocketAddrExt;
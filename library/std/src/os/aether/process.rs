@@ -0,0 +1,40 @@
+//! aether-specific extensions to primitives in the [`std::process`] module.
+//!
+//! [`std::process`]: crate::process
+
+#![unstable(feature = "aether_ext", issue = "none")]
+
+use crate::os::fd::OwnedFd;
+use crate::sealed::Sealed;
+use crate::{io, process};
+
+/// A kernel process id.
+#[unstable(feature = "aether_ext", issue = "none")]
+pub type RawPid = i32;
+
+/// aether-specific extensions to [`Child`].
+///
+/// [`Child`]: process::Child
+#[unstable(feature = "aether_ext", issue = "none")]
+pub trait ChildExt: Sealed {
+    /// Returns the kernel process id of the child.
+    ///
+    /// This is the same value as [`Child::id`], in the type the kernel uses.
+    ///
+    /// [`Child::id`]: process::Child::id
+    fn raw_pid(&self) -> RawPid;
+
+    /// Opens a new handle to the child process.
+    ///
+    /// The handle keeps referring to this child even after its pid has been
+    /// reused, so it can be passed to other processes or services that need
+    /// to signal or wait on it. Each call returns an independent handle with
+    /// `FD_CLOEXEC` set.
+    ///
+    /// Returns an error if the child has already been reaped by
+    /// [`Child::wait`] or [`Child::try_wait`].
+    ///
+    /// [`Child::wait`]: process::Child::wait
+    /// [`Child::try_wait`]: process::Child::try_wait
+    fn duplicate_handle(&self) -> io::Result<OwnedFd>;
+}
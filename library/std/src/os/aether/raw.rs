@@ -1,6 +1,9 @@
-//! Redox-specific raw type definitions
+//! aether-specific raw type definitions
+//!
+//! Unlike the `raw` modules of older platforms, these definitions were never
+//! stable, so they follow aether's libc instead of a frozen layout.
 
-#![stable(feature = "raw_ext", since = "1.1.0")]
+#![unstable(feature = "aether_raw", issue = "none")]
 #![deprecated(
     since = "1.8.0",
     note = "these type aliases are no longer supported by \
@@ -10,69 +13,117 @@
 )]
 #![allow(deprecated)]
 
-use crate::os::raw::{c_char, c_int, c_long, c_ulong, c_void};
+use crate::os::raw::{c_long, c_void};
 
-#[stable(feature = "raw_ext", since = "1.1.0")]
-pub type dev_t = c_long;
-#[stable(feature = "raw_ext", since = "1.1.0")]
-pub type gid_t = c_int;
-#[stable(feature = "raw_ext", since = "1.1.0")]
-pub type mode_t = c_int;
-#[stable(feature = "raw_ext", since = "1.1.0")]
-pub type uid_t = c_int;
+#[unstable(feature = "aether_raw", issue = "none")]
+pub type dev_t = u64;
+#[unstable(feature = "aether_raw", issue = "none")]
+pub type gid_t = u32;
+#[unstable(feature = "aether_raw", issue = "none")]
+pub type mode_t = u32;
+#[unstable(feature = "aether_raw", issue = "none")]
+pub type uid_t = u32;
 
-#[stable(feature = "pthread_t", since = "1.8.0")]
+#[unstable(feature = "aether_raw", issue = "none")]
 pub type pthread_t = *mut c_void;
 
-#[stable(feature = "raw_ext", since = "1.1.0")]
-pub type blkcnt_t = c_ulong;
-#[stable(feature = "raw_ext", since = "1.1.0")]
-pub type blksize_t = c_ulong;
-#[stable(feature = "raw_ext", since = "1.1.0")]
-pub type ino_t = c_ulong;
-#[stable(feature = "raw_ext", since = "1.1.0")]
-pub type nlink_t = c_ulong;
-#[stable(feature = "raw_ext", since = "1.1.0")]
-pub type off_t = c_long;
-#[stable(feature = "raw_ext", since = "1.1.0")]
-pub type time_t = c_long;
+#[unstable(feature = "aether_raw", issue = "none")]
+pub type blkcnt_t = i64;
+#[unstable(feature = "aether_raw", issue = "none")]
+pub type blksize_t = i64;
+#[unstable(feature = "aether_raw", issue = "none")]
+pub type ino_t = u64;
+#[unstable(feature = "aether_raw", issue = "none")]
+pub type nlink_t = u64;
+#[unstable(feature = "aether_raw", issue = "none")]
+pub type off_t = i64;
+#[unstable(feature = "aether_raw", issue = "none")]
+pub type time_t = i64;
 
+/// Mirrors the `stat` of aether's libc, which keeps the x86_64 Linux layout
+/// and stores the birth time in what Linux leaves reserved.
 #[repr(C)]
 #[derive(Clone)]
-#[stable(feature = "raw_ext", since = "1.1.0")]
+#[unstable(feature = "aether_raw", issue = "none")]
 pub struct stat {
-    #[stable(feature = "raw_ext", since = "1.1.0")]
+    #[unstable(feature = "aether_raw", issue = "none")]
     pub st_dev: dev_t,
-    #[stable(feature = "raw_ext", since = "1.1.0")]
+    #[unstable(feature = "aether_raw", issue = "none")]
     pub st_ino: ino_t,
-    #[stable(feature = "raw_ext", since = "1.1.0")]
+    #[unstable(feature = "aether_raw", issue = "none")]
     pub st_nlink: nlink_t,
-    #[stable(feature = "raw_ext", since = "1.1.0")]
+    #[unstable(feature = "aether_raw", issue = "none")]
     pub st_mode: mode_t,
-    #[stable(feature = "raw_ext", since = "1.1.0")]
+    #[unstable(feature = "aether_raw", issue = "none")]
     pub st_uid: uid_t,
-    #[stable(feature = "raw_ext", since = "1.1.0")]
+    #[unstable(feature = "aether_raw", issue = "none")]
     pub st_gid: gid_t,
-    #[stable(feature = "raw_ext", since = "1.1.0")]
+    __pad0: u32,
+    #[unstable(feature = "aether_raw", issue = "none")]
     pub st_rdev: dev_t,
-    #[stable(feature = "raw_ext", since = "1.1.0")]
+    #[unstable(feature = "aether_raw", issue = "none")]
     pub st_size: off_t,
-    #[stable(feature = "raw_ext", since = "1.1.0")]
+    #[unstable(feature = "aether_raw", issue = "none")]
     pub st_blksize: blksize_t,
-    #[stable(feature = "raw_ext", since = "1.1.0")]
+    #[unstable(feature = "aether_raw", issue = "none")]
     pub st_blocks: blkcnt_t,
-    #[stable(feature = "raw_ext", since = "1.1.0")]
+    #[unstable(feature = "aether_raw", issue = "none")]
     pub st_atime: time_t,
-    #[stable(feature = "raw_ext", since = "1.1.0")]
+    #[unstable(feature = "aether_raw", issue = "none")]
     pub st_atime_nsec: c_long,
-    #[stable(feature = "raw_ext", since = "1.1.0")]
+    #[unstable(feature = "aether_raw", issue = "none")]
     pub st_mtime: time_t,
-    #[stable(feature = "raw_ext", since = "1.1.0")]
+    #[unstable(feature = "aether_raw", issue = "none")]
     pub st_mtime_nsec: c_long,
-    #[stable(feature = "raw_ext", since = "1.1.0")]
+    #[unstable(feature = "aether_raw", issue = "none")]
     pub st_ctime: time_t,
-    #[stable(feature = "raw_ext", since = "1.1.0")]
+    #[unstable(feature = "aether_raw", issue = "none")]
     pub st_ctime_nsec: c_long,
-    #[stable(feature = "raw_ext", since = "1.1.0")]
-    pub _pad: [c_char; 24],
+    #[unstable(feature = "aether_raw", issue = "none")]
+    pub st_birthtime: time_t,
+    #[unstable(feature = "aether_raw", issue = "none")]
+    pub st_birthtime_nsec: c_long,
+    __unused: [c_long; 1],
 }
+
+// `MetadataExt::as_raw_stat` reinterprets a `libc::stat` as this struct, so
+// the two must agree exactly.
+const _: () = {
+    use crate::mem::offset_of;
+
+    const fn field_size<T, F>(_: fn(&T) -> &F) -> usize {
+        size_of::<F>()
+    }
+
+    macro_rules! assert_same_fields {
+        ($($field:ident),* $(,)?) => {$(
+            assert!(offset_of!(stat, $field) == offset_of!(libc::stat, $field));
+            assert!(
+                field_size(|s: &stat| &s.$field) == field_size(|s: &libc::stat| &s.$field)
+            );
+        )*};
+    }
+
+    assert!(size_of::<stat>() == size_of::<libc::stat>());
+    assert!(align_of::<stat>() == align_of::<libc::stat>());
+    assert_same_fields!(
+        st_dev,
+        st_ino,
+        st_nlink,
+        st_mode,
+        st_uid,
+        st_gid,
+        st_rdev,
+        st_size,
+        st_blksize,
+        st_blocks,
+        st_atime,
+        st_atime_nsec,
+        st_mtime,
+        st_mtime_nsec,
+        st_ctime,
+        st_ctime_nsec,
+        st_birthtime,
+        st_birthtime_nsec,
+    );
+};
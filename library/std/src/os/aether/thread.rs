@@ -0,0 +1,46 @@
+//! aether-specific extensions to primitives in the [`std::thread`] module.
+//!
+//! [`std::thread`]: crate::thread
+
+#![unstable(feature = "aether_ext", issue = "none")]
+
+use crate::io;
+use crate::sys_common::AsInner;
+use crate::thread::JoinHandle;
+
+/// A kernel thread id.
+#[unstable(feature = "aether_ext", issue = "none")]
+pub type RawTid = i32;
+
+/// aether-specific extensions to [`JoinHandle`].
+#[unstable(feature = "aether_ext", issue = "none")]
+pub trait JoinHandleExt {
+    /// Returns the kernel id of the thread.
+    ///
+    /// Fails once the thread has exited, even if it hasn't been joined yet.
+    fn native_id(&self) -> io::Result<RawTid>;
+
+    /// Returns the indices of the CPUs the thread may run on.
+    fn cpu_affinity(&self) -> io::Result<Vec<usize>>;
+
+    /// Restricts the thread to the CPUs whose indices are in `cpus`.
+    ///
+    /// Fails if none of `cpus` is online, or if an index is beyond the
+    /// largest CPU count aether supports.
+    fn set_cpu_affinity(&self, cpus: &[usize]) -> io::Result<()>;
+}
+
+#[unstable(feature = "aether_ext", issue = "none")]
+impl<T> JoinHandleExt for JoinHandle<T> {
+    fn native_id(&self) -> io::Result<RawTid> {
+        self.as_inner().native_id()
+    }
+
+    fn cpu_affinity(&self) -> io::Result<Vec<usize>> {
+        self.as_inner().affinity()
+    }
+
+    fn set_cpu_affinity(&self, cpus: &[usize]) -> io::Result<()> {
+        self.as_inner().set_affinity(cpus)
+    }
+}
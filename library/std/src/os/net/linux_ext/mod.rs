@@ -1,15 +1,19 @@
 //! Linux and Android-specific networking functionality.
+//!
+//! aether only shares the abstract socket namespace, so it gets `addr` alone.
 
-#![doc(cfg(any(target_os = "linux", target_os = "android")))]
+#![doc(cfg(any(target_os = "linux", target_os = "android", target_os = "aether")))]
 
 #[stable(feature = "unix_socket_abstract", since = "1.70.0")]
 pub(crate) mod addr;
 
+#[cfg(any(target_os = "linux", target_os = "android", doc))]
 #[unstable(feature = "unix_socket_ancillary_data", issue = "76915")]
 pub(crate) mod socket;
 
+#[cfg(any(target_os = "linux", target_os = "android", doc))]
 #[unstable(feature = "tcp_quickack", issue = "96256")]
 pub(crate) mod tcp;
 
-#[cfg(test)]
+#[cfg(all(test, any(target_os = "linux", target_os = "android")))]
 mod tests;
@@ -197,12 +197,9 @@ fn lang_start<T: crate::process::Termination + 'static>(
 ) -> isize {
     lang_start_internal(
         &move || {
-            let result = main();
-
-            // prevent this frame from being tail-call optimised away
-            crate::hint::black_box(());
-
-            result.report().to_i32()
+            crate::sys::backtrace::__rust_begin_short_backtrace(main)
+                .report()
+                .to_i32()
         },
         argc,
         argv,
@@ -1,22 +1,195 @@
-//! Global Allocator for UEFI.
-//! Uses [r-efi-alloc](https://crates.io/crates/r-efi-alloc)
+//! Global Allocator for aether.
+//!
+//! aether's libc is musl-derived, so `malloc` only guarantees `MIN_ALIGN` and
+//! larger alignments have to go through `posix_memalign`.
+//!
+//! The allocator is implemented as free functions so that the unit tests of
+//! Linux hosts, which provide the same libc entry points, can exercise the
+//! alignment handling without an aether machine. Those hosts keep using the
+//! `unix` allocator as `System`.
+//!
+//! When std is built with `-Zsanitizer=kernel-address`, the allocator tells
+//! the KASAN runtime which bytes belong to a live allocation. libc's `malloc`
+//! is not instrumented, so without this freed blocks would still read as
+//! addressable and use-after-free accesses from Rust would go unnoticed.
+//! Conversely, libc must never get back a block that is still poisoned, or
+//! whoever it hands the block to next trips a false use-after-free. Freed
+//! blocks are therefore held in a quarantine, poisoned, and only unpoisoned
+//! and returned to libc once enough newer blocks have been freed.
 
-use crate::alloc::{GlobalAlloc, Layout, System};
-use crate::sys::pal::helpers;
+use super::{MIN_ALIGN, realloc_fallback};
+#[cfg(target_os = "aether")]
+use crate::alloc::GlobalAlloc;
+use crate::alloc::{Layout, System};
+use crate::ptr;
 
-unsafe extern "C" {
-    fn malloc(size: usize) -> *mut core::ffi::c_void;
-    fn free(ptr: *mut core::fi::c_void);
-}
+#[cfg(test)]
+mod tests;
 
+#[cfg(target_os = "aether")]
 #[stable(feature = "alloc_system_type", since = "1.28.0")]
 unsafe impl GlobalAlloc for System {
+    #[inline]
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
-        return unsafe { malloc(layout.size()) } as *mut u8;
-        // todo: align malloc
+        unsafe { alloc_impl(layout) }
+    }
+
+    #[inline]
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        unsafe { alloc_zeroed_impl(layout) }
     }
 
+    #[inline]
     unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
-        return unsafe { free(ptr as *mut core::ffi::c_void) };
+        unsafe { dealloc_impl(ptr, layout) }
+    }
+
+    #[inline]
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        unsafe { realloc_impl(ptr, layout, new_size) }
+    }
+}
+
+#[inline]
+pub(super) unsafe fn alloc_impl(layout: Layout) -> *mut u8 {
+    // Only rely on MIN_ALIGN if size >= align, see the comment in `unix.rs`.
+    let ptr = if layout.align() <= MIN_ALIGN && layout.align() <= layout.size() {
+        unsafe { libc::malloc(layout.size()) as *mut u8 }
+    } else {
+        unsafe { aligned_malloc(&layout) }
+    };
+    unsafe { kasan::unpoison(ptr, layout.size()) };
+    ptr
+}
+
+#[inline]
+pub(super) unsafe fn alloc_zeroed_impl(layout: Layout) -> *mut u8 {
+    // See the comment above in `alloc_impl` for why this check looks the way it does.
+    if layout.align() <= MIN_ALIGN && layout.align() <= layout.size() {
+        let ptr = unsafe { libc::calloc(layout.size(), 1) as *mut u8 };
+        unsafe { kasan::unpoison(ptr, layout.size()) };
+        ptr
+    } else {
+        let ptr = unsafe { aligned_malloc(&layout) };
+        if !ptr.is_null() {
+            unsafe { kasan::unpoison(ptr, layout.size()) };
+            unsafe { ptr::write_bytes(ptr, 0, layout.size()) };
+        }
+        ptr
+    }
+}
+
+#[inline]
+pub(super) unsafe fn dealloc_impl(ptr: *mut u8, layout: Layout) {
+    unsafe { kasan::free(ptr, layout.size()) }
+}
+
+#[inline]
+pub(super) unsafe fn realloc_impl(ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+    // `realloc` may move the block to an address that only satisfies
+    // `MIN_ALIGN`, and by then the original block is gone. Over-aligned
+    // blocks are therefore always moved through a fresh aligned allocation.
+    // So are all blocks under KASAN, where libc must not free the old block
+    // before it went through the quarantine.
+    if !kasan::ENABLED && layout.align() <= MIN_ALIGN && layout.align() <= new_size {
+        unsafe { libc::realloc(ptr as *mut libc::c_void, new_size) as *mut u8 }
+    } else {
+        unsafe { realloc_fallback(&System, ptr, layout, new_size) }
+    }
+}
+
+#[inline]
+unsafe fn aligned_malloc(layout: &Layout) -> *mut u8 {
+    let mut out = ptr::null_mut();
+    // `posix_memalign` requires the alignment to be a multiple of `sizeof(void*)`.
+    // Since these are all powers of 2, we can just use max.
+    let align = layout.align().max(size_of::<usize>());
+    let ret = unsafe { libc::posix_memalign(&mut out, align, layout.size()) };
+    if ret != 0 {
+        ptr::null_mut()
+    } else {
+        out as *mut u8
+    }
+}
+
+/// Hooks into the runtime of the kernel address sanitizer.
+#[cfg(sanitize = "kernel-address")]
+mod kasan {
+    use crate::cell::UnsafeCell;
+    use crate::sys::sync::Mutex;
+
+    unsafe extern "C" {
+        fn __asan_poison_memory_region(addr: *const u8, size: usize);
+        fn __asan_unpoison_memory_region(addr: *const u8, size: usize);
+    }
+
+    pub const ENABLED: bool = true;
+
+    /// How many freed blocks stay poisoned before they go back to libc.
+    pub const QUARANTINE_LEN: usize = 256;
+
+    /// A ring of freed blocks, oldest first from `next`.
+    struct Quarantine {
+        lock: Mutex,
+        blocks: UnsafeCell<[(*mut u8, usize); QUARANTINE_LEN]>,
+        next: UnsafeCell<usize>,
+    }
+
+    // SAFETY: `blocks` and `next` are only accessed with `lock` held.
+    unsafe impl Sync for Quarantine {}
+
+    static QUARANTINE: Quarantine = Quarantine {
+        lock: Mutex::new(),
+        blocks: UnsafeCell::new([(crate::ptr::null_mut(), 0); QUARANTINE_LEN]),
+        next: UnsafeCell::new(0),
+    };
+
+    /// Marks `size` bytes at `ptr` as accessible.
+    #[inline]
+    pub unsafe fn unpoison(ptr: *const u8, size: usize) {
+        if !ptr.is_null() && size != 0 {
+            unsafe { __asan_unpoison_memory_region(ptr, size) }
+        }
+    }
+
+    /// Poisons the `size` bytes of the freed block at `ptr` and quarantines
+    /// it, returning the oldest quarantined block to libc instead.
+    pub unsafe fn free(ptr: *mut u8, size: usize) {
+        if ptr.is_null() {
+            return;
+        }
+        if size != 0 {
+            unsafe { __asan_poison_memory_region(ptr, size) }
+        }
+
+        QUARANTINE.lock.lock();
+        // SAFETY: the lock is held.
+        let (old, old_size) = unsafe {
+            let next = &mut *QUARANTINE.next.get();
+            let old = crate::mem::replace(&mut (*QUARANTINE.blocks.get())[*next], (ptr, size));
+            *next = (*next + 1) % QUARANTINE_LEN;
+            old
+        };
+        unsafe { QUARANTINE.lock.unlock() };
+
+        if !old.is_null() {
+            unsafe {
+                unpoison(old, old_size);
+                libc::free(old as *mut libc::c_void);
+            }
+        }
+    }
+}
+
+#[cfg(not(sanitize = "kernel-address"))]
+mod kasan {
+    pub const ENABLED: bool = false;
+
+    #[inline(always)]
+    pub unsafe fn unpoison(_ptr: *const u8, _size: usize) {}
+
+    #[inline(always)]
+    pub unsafe fn free(ptr: *mut u8, _size: usize) {
+        unsafe { libc::free(ptr as *mut libc::c_void) }
     }
 }
@@ -0,0 +1,217 @@
+//! On aether, `Box`, `Vec` and `alloc::alloc` go through this allocator as
+//! `System`. Linux hosts route them to their own `unix` allocator, so the
+//! tests further down also call the aether functions directly.
+
+use super::{alloc_impl, alloc_zeroed_impl, dealloc_impl, realloc_impl};
+use crate::alloc::{self, GlobalAlloc, Layout, System};
+
+macro_rules! for_each_align {
+    ($f:ident) => {{
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        #[repr(C, align(1))]
+        struct A1(u8);
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        #[repr(C, align(2))]
+        struct A2(u8);
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        #[repr(C, align(4))]
+        struct A4(u8);
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        #[repr(C, align(8))]
+        struct A8(u8);
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        #[repr(C, align(16))]
+        struct A16(u8);
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        #[repr(C, align(32))]
+        struct A32(u8);
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        #[repr(C, align(64))]
+        struct A64(u8);
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        #[repr(C, align(128))]
+        struct A128(u8);
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        #[repr(C, align(256))]
+        struct A256(u8);
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        #[repr(C, align(512))]
+        struct A512(u8);
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        #[repr(C, align(1024))]
+        struct A1024(u8);
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        #[repr(C, align(2048))]
+        struct A2048(u8);
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        #[repr(C, align(4096))]
+        struct A4096(u8);
+
+        $f(A1);
+        $f(A2);
+        $f(A4);
+        $f(A8);
+        $f(A16);
+        $f(A32);
+        $f(A64);
+        $f(A128);
+        $f(A256);
+        $f(A512);
+        $f(A1024);
+        $f(A2048);
+        $f(A4096);
+    }};
+}
+
+fn is_aligned<T>(ptr: *const T, align: usize) -> bool {
+    ptr.addr() % align == 0
+}
+
+#[test]
+fn box_alignment() {
+    fn check<T: Copy + PartialEq + crate::fmt::Debug>(f: fn(u8) -> T) {
+        let b = Box::new(f(0xa5));
+        assert!(is_aligned(&*b as *const T, align_of::<T>()));
+        assert_eq!(*b, f(0xa5));
+    }
+    for_each_align!(check);
+}
+
+#[test]
+fn vec_alignment_across_growth() {
+    fn check<T: Copy + PartialEq + crate::fmt::Debug>(f: fn(u8) -> T) {
+        let mut v = Vec::new();
+        for i in 0..64u8 {
+            v.push(f(i));
+            assert!(is_aligned(v.as_ptr(), align_of::<T>()));
+        }
+        v.shrink_to_fit();
+        assert!(is_aligned(v.as_ptr(), align_of::<T>()));
+        assert!(v.iter().enumerate().all(|(i, x)| *x == f(i as u8)));
+    }
+    for_each_align!(check);
+}
+
+#[test]
+fn global_alloc_alignment() {
+    let mut align = 1;
+    while align <= 4096 {
+        let layout = Layout::from_size_align(align, align).unwrap();
+        unsafe {
+            let p = alloc::alloc(layout);
+            assert!(!p.is_null());
+            assert!(is_aligned(p, align));
+            for i in 0..align {
+                *p.add(i) = i as u8;
+            }
+
+            let grown = alloc::realloc(p, layout, align * 3);
+            assert!(!grown.is_null());
+            assert!(is_aligned(grown, align));
+            assert!((0..align).all(|i| *grown.add(i) == i as u8));
+            alloc::dealloc(grown, Layout::from_size_align(align * 3, align).unwrap());
+
+            let zeroed = System.alloc_zeroed(layout);
+            assert!(!zeroed.is_null());
+            assert!(is_aligned(zeroed, align));
+            assert!((0..align).all(|i| *zeroed.add(i) == 0));
+            System.dealloc(zeroed, layout);
+        }
+        align *= 2;
+    }
+}
+
+#[test]
+fn raw_alloc_alignment() {
+    let mut align = 1;
+    while align <= 4096 {
+        // Cover sizes below, equal to, and above the alignment, since small
+        // sizes take the `malloc` fast path.
+        for size in [1, align / 2 + 1, align, align * 3] {
+            let layout = Layout::from_size_align(size, align).unwrap();
+            unsafe {
+                let p = alloc_impl(layout);
+                assert!(!p.is_null());
+                assert!(is_aligned(p, align), "size {size} align {align}");
+                p.write_bytes(0xff, size);
+                dealloc_impl(p, layout);
+            }
+        }
+        align *= 2;
+    }
+}
+
+#[test]
+fn alloc_zeroed_is_zeroed() {
+    let mut align = 1;
+    while align <= 4096 {
+        let size = align * 2 + 3;
+        let layout = Layout::from_size_align(size, align).unwrap();
+        unsafe {
+            let p = alloc_zeroed_impl(layout);
+            assert!(!p.is_null());
+            assert!(is_aligned(p, align));
+            assert!(
+                crate::slice::from_raw_parts(p, size)
+                    .iter()
+                    .all(|&b| b == 0)
+            );
+            dealloc_impl(p, layout);
+        }
+        align *= 2;
+    }
+}
+
+#[test]
+fn realloc_preserves_contents_and_alignment() {
+    let mut align = 1;
+    while align <= 4096 {
+        let layout = Layout::from_size_align(align, align).unwrap();
+        unsafe {
+            let p = alloc_impl(layout);
+            assert!(!p.is_null());
+            for i in 0..align {
+                *p.add(i) = i as u8;
+            }
+
+            let grown = realloc_impl(p, layout, align * 5);
+            assert!(!grown.is_null());
+            assert!(is_aligned(grown, align));
+            assert!((0..align).all(|i| *grown.add(i) == i as u8));
+
+            let grown_layout = Layout::from_size_align(align * 5, align).unwrap();
+            let shrunk = realloc_impl(grown, grown_layout, 1);
+            assert!(!shrunk.is_null());
+            assert!(is_aligned(shrunk, align));
+            assert_eq!(*shrunk, 0);
+
+            dealloc_impl(shrunk, Layout::from_size_align(1, align).unwrap());
+        }
+        align *= 2;
+    }
+}
+
+#[test]
+#[cfg(sanitize = "kernel-address")]
+fn blocks_leave_the_quarantine_unpoisoned() {
+    unsafe extern "C" {
+        fn __asan_region_is_poisoned(addr: *const u8, size: usize) -> *const u8;
+    }
+
+    // Push every block that might still be quarantined back to libc, then
+    // make sure whatever libc hands out next is addressable again.
+    let layout = Layout::from_size_align(64, 8).unwrap();
+    for _ in 0..super::kasan::QUARANTINE_LEN * 2 {
+        unsafe {
+            let p = alloc_impl(layout);
+            assert!(!p.is_null());
+            dealloc_impl(p, layout);
+        }
+    }
+    unsafe {
+        let p = libc::malloc(layout.size()) as *mut u8;
+        assert!(!p.is_null());
+        assert!(__asan_region_is_poisoned(p, layout.size()).is_null());
+        libc::free(p as *mut libc::c_void);
+    }
+}
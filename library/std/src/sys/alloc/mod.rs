@@ -71,15 +71,16 @@ unsafe fn realloc_fallback(
 }
 
 cfg_if::cfg_if! {
-    if #[cfg(any(
+    // aether is part of the unix family, so it has to be matched first.
+    if #[cfg(target_os = "aether")] {
+        mod aether;
+    } else if #[cfg(any(
         target_family = "unix",
         target_os = "wasi",
         target_os = "teeos",
         target_os = "trusty",
     ))] {
         mod unix;
-    } else if #[cfg(target_os = "aether")] {
-        mod aether;
     } else if #[cfg(target_os = "windows")] {
         mod windows;
     } else if #[cfg(target_os = "hermit")] {
@@ -98,3 +99,8 @@ cfg_if::cfg_if! {
         mod zkvm;
     }
 }
+
+// Linux hosts test aether's allocator functions off-target, next to their own
+// `System` allocator.
+#[cfg(all(test, target_os = "linux"))]
+mod aether;
@@ -587,7 +587,23 @@ impl FileAttr {
         )
     }
 
+    #[cfg(target_os = "aether")]
+    pub fn created(&self) -> io::Result<SystemTime> {
+        // A zero birth time means the filesystem doesn't record it.
+        if self.stat.st_birthtime == 0 && self.stat.st_birthtime_nsec == 0 {
+            return Err(io::const_error!(
+                io::ErrorKind::Unsupported,
+                "creation time is not available for the filesystem",
+            ));
+        }
+        SystemTime::new(
+            self.stat.st_birthtime as i64,
+            self.stat.st_birthtime_nsec as i64,
+        )
+    }
+
     #[cfg(not(any(
+        target_os = "aether",
         target_os = "freebsd",
         target_os = "openbsd",
         target_os = "vita",
@@ -2124,7 +2140,7 @@ mod cfm {
         }
     }
 }
-#[cfg(any(target_os = "linux", target_os = "android"))]
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "aether"))]
 pub(crate) use cfm::CachedFileMetadata;
 
 #[cfg(not(target_vendor = "apple"))]
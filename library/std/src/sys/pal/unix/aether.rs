@@ -1,313 +1,247 @@
-#![allow(non_camel_case_types, unused)]
-
-use libc::{c_int, c_void, size_t};
-
-use crate::io;
-use crate::mem::MaybeUninit;
-use crate::os::raw::c_char;
-
-pub type zx_handle_t = u32;
-pub type zx_vaddr_t = usize;
-pub type zx_rights_t = u32;
-pub type zx_status_t = i32;
-
-pub const ZX_HANDLE_INVALID: zx_handle_t = 0;
+//! The aether kernel interface.
+//!
+//! aether implements the x86_64 Linux system call convention: the call number
+//! goes in `rax`, up to six arguments in `rdi`, `rsi`, `rdx`, `r10`, `r8` and
+//! `r9`, and a failure comes back as a negated errno in `-4095..=-1`. The
+//! libc shipped in aether images only wraps a subset of the calls, so std
+//! issues the ones it depends on directly through this module.
+//!
+//! Because the convention matches Linux, this module is also built for the
+//! unit tests of x86_64 Linux hosts, where the same calls reach the host
+//! kernel.
+
+use crate::arch::asm;
+use crate::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
+use crate::{fmt, io};
+
+#[cfg(test)]
+mod tests;
+
+/// System call numbers.
+// Some are only issued through `syscall!` on aether itself, not by the host
+// unit tests.
+#[cfg_attr(not(target_os = "aether"), allow(dead_code))]
+pub mod nr {
+    pub const SYS_close: usize = 3;
+    pub const SYS_sched_yield: usize = 24;
+    pub const SYS_getpid: usize = 39;
+    pub const SYS_sendfile: usize = 40;
+    pub const SYS_kill: usize = 62;
+    pub const SYS_futex: usize = 202;
+    pub const SYS_sched_setaffinity: usize = 203;
+    pub const SYS_sched_getaffinity: usize = 204;
+    pub const SYS_splice: usize = 275;
+    pub const SYS_getrandom: usize = 318;
+    pub const SYS_copy_file_range: usize = 326;
+    pub const SYS_pidfd_open: usize = 434;
+}
 
-pub type zx_time_t = i64;
-pub const ZX_TIME_INFINITE: zx_time_t = i64::MAX;
+// `futex` operations.
+pub const FUTEX_WAKE: libc::c_int = 1;
+pub const FUTEX_WAIT_BITSET: libc::c_int = 9;
+pub const FUTEX_PRIVATE_FLAG: libc::c_int = 128;
 
-pub type zx_signals_t = u32;
+/// The largest errno the kernel reports through a system call return value.
+const MAX_ERRNO: isize = 4095;
 
-pub const ZX_OBJECT_SIGNAL_3: zx_signals_t = 1 << 3;
+/// An error number reported by the kernel.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Errno(pub libc::c_int);
 
-pub const ZX_TASK_TERMINATED: zx_signals_t = ZX_OBJECT_SIGNAL_3;
+impl Errno {
+    /// Decodes the return value of a raw system call.
+    #[inline]
+    pub fn from_ret(ret: isize) -> Result<usize, Errno> {
+        if (-MAX_ERRNO..0).contains(&ret) {
+            Err(Errno(-ret as libc::c_int))
+        } else {
+            Ok(ret as usize)
+        }
+    }
+}
 
-pub const ZX_RIGHT_SAME_RIGHTS: zx_rights_t = 1 << 31;
+impl fmt::Debug for Errno {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Errno").field(&self.0).finish()
+    }
+}
 
-// The upper four bits gives the minor version.
-pub type zx_object_info_topic_t = u32;
+impl From<Errno> for io::Error {
+    #[inline]
+    fn from(errno: Errno) -> io::Error {
+        io::Error::from_raw_os_error(errno.0)
+    }
+}
 
-pub const ZX_INFO_PROCESS: zx_object_info_topic_t = 3 | (1 << 28);
+/// Converts the return value of a raw system call into an `io::Result`.
+#[inline]
+pub fn cvt(ret: isize) -> io::Result<usize> {
+    Errno::from_ret(ret).map_err(io::Error::from)
+}
 
-pub type zx_info_process_flags_t = u32;
+/// Issues a system call with up to six arguments, returning the raw result.
+///
+/// # Safety
+///
+/// The arguments must be valid for the requested call.
+#[inline]
+pub unsafe fn syscall(nr: usize, args: &[usize]) -> isize {
+    debug_assert!(args.len() <= 6);
+    let arg = |i: usize| args.get(i).copied().unwrap_or(0);
+    let ret: isize;
+    unsafe {
+        asm!(
+            "syscall",
+            inlateout("rax") nr as isize => ret,
+            in("rdi") arg(0),
+            in("rsi") arg(1),
+            in("rdx") arg(2),
+            in("r10") arg(3),
+            in("r8") arg(4),
+            in("r9") arg(5),
+            lateout("rcx") _,
+            lateout("r11") _,
+            options(nostack, preserves_flags),
+        );
+    }
+    ret
+}
 
-pub fn zx_cvt<T>(t: T) -> io::Result<T>
-where
-    T: TryInto<zx_status_t> + Copy,
-{
-    if let Ok(status) = TryInto::try_into(t) {
-        if status < 0 {
-            Err(io::Error::from_raw_os_error(status))
-        } else {
-            Ok(t)
+/// Issues a system call and reports failure the way libc does: by returning
+/// `-1` and setting `errno`. This is what the `syscall!` macro falls back to.
+///
+/// # Safety
+///
+/// The arguments must be valid for the requested call.
+#[inline]
+pub unsafe fn syscall_errno(nr: usize, args: &[usize]) -> isize {
+    match Errno::from_ret(unsafe { syscall(nr, args) }) {
+        Ok(ret) => ret as isize,
+        Err(Errno(errno)) => {
+            super::os::set_errno(errno);
+            -1
         }
-    } else {
-        Err(io::Error::last_os_error())
     }
 }
 
-// Safe wrapper around zx_handle_t
+/// An owned kernel handle.
+///
+/// Every kernel object aether hands to userspace is named by a file
+/// descriptor. The descriptor is closed when the handle is dropped.
 pub struct Handle {
-    raw: zx_handle_t,
+    fd: RawFd,
 }
 
 impl Handle {
-    pub fn new(raw: zx_handle_t) -> Handle {
-        Handle { raw }
-    }
-
-    pub fn raw(&self) -> zx_handle_t {
-        self.raw
+    /// Takes ownership of `fd`.
+    ///
+    /// # Safety
+    ///
+    /// `fd` must be open and not owned by anything else.
+    #[inline]
+    pub unsafe fn from_raw(fd: RawFd) -> Handle {
+        assert_ne!(fd, -1);
+        Handle { fd }
     }
 }
 
 impl Drop for Handle {
     fn drop(&mut self) {
-        unsafe {
-            zx_cvt(zx_handle_close(self.raw)).expect("Failed to close zx_handle_t");
-        }
+        // Like `OwnedFd`, errors are ignored: the descriptor is released even
+        // if `close` reports EINTR, so retrying could close an unrelated fd.
+        let _ = unsafe { syscall(nr::SYS_close, &[self.fd as usize]) };
     }
 }
 
-// Returned for topic ZX_INFO_PROCESS
-#[derive(Default)]
-#[repr(C)]
-pub struct zx_info_process_t {
-    pub return_code: i64,
-    pub start_time: zx_time_t,
-    pub flags: zx_info_process_flags_t,
-    pub reserved1: u32,
+impl fmt::Debug for Handle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Handle").field("fd", &self.fd).finish()
+    }
 }
 
-unsafe extern "C" {
-    pub fn zx_job_default() -> zx_handle_t;
-
-    pub fn zx_task_kill(handle: zx_handle_t) -> zx_status_t;
-
-    pub fn zx_handle_close(handle: zx_handle_t) -> zx_status_t;
-
-    pub fn zx_handle_duplicate(
-        handle: zx_handle_t,
-        rights: zx_rights_t,
-        out: *const zx_handle_t,
-    ) -> zx_handle_t;
-
-    pub fn zx_object_wait_one(
-        handle: zx_handle_t,
-        signals: zx_signals_t,
-        timeout: zx_time_t,
-        pending: *mut zx_signals_t,
-    ) -> zx_status_t;
-
-    pub fn zx_object_get_info(
-        handle: zx_handle_t,
-        topic: u32,
-        buffer: *mut c_void,
-        buffer_size: size_t,
-        actual_size: *mut size_t,
-        avail: *mut size_t,
-    ) -> zx_status_t;
+impl AsRawFd for Handle {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
 }
 
-#[derive(Default)]
-#[repr(C)]
-pub struct fdio_spawn_action_t {
-    pub action: u32,
-    pub reserved0: u32,
-    pub local_fd: i32,
-    pub target_fd: i32,
-    pub reserved1: u64,
+impl AsFd for Handle {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        unsafe { BorrowedFd::borrow_raw(self.fd) }
+    }
 }
 
-unsafe extern "C" {
-    pub fn fdio_spawn_etc(
-        job: zx_handle_t,
-        flags: u32,
-        path: *const c_char,
-        argv: *const *const c_char,
-        envp: *const *const c_char,
-        action_count: size_t,
-        actions: *const fdio_spawn_action_t,
-        process: *mut zx_handle_t,
-        err_msg: *mut c_char,
-    ) -> zx_status_t;
-
-    pub fn fdio_fd_clone(fd: c_int, out_handle: *mut zx_handle_t) -> zx_status_t;
-    pub fn fdio_fd_create(handle: zx_handle_t, fd: *mut c_int) -> zx_status_t;
+impl IntoRawFd for Handle {
+    #[inline]
+    fn into_raw_fd(self) -> RawFd {
+        let fd = self.fd;
+        crate::mem::forget(self);
+        fd
+    }
 }
 
-// fdio_spawn_etc flags
-
-pub const FDIO_SPAWN_CLONE_JOB: u32 = 0x0001;
-pub const FDIO_SPAWN_CLONE_LDSVC: u32 = 0x0002;
-pub const FDIO_SPAWN_CLONE_NAMESPACE: u32 = 0x0004;
-pub const FDIO_SPAWN_CLONE_STDIO: u32 = 0x0008;
-pub const FDIO_SPAWN_CLONE_ENVIRON: u32 = 0x0010;
-pub const FDIO_SPAWN_CLONE_UTC_CLOCK: u32 = 0x0020;
-pub const FDIO_SPAWN_CLONE_ALL: u32 = 0xFFFF;
-
-// fdio_spawn_etc actions
-
-pub const FDIO_SPAWN_ACTION_CLONE_FD: u32 = 0x0001;
-pub const FDIO_SPAWN_ACTION_TRANSFER_FD: u32 = 0x0002;
-
-// Errors
-
-#[allow(unused)]
-pub const ERR_INTERNAL: zx_status_t = -1;
-
-// ERR_NOT_SUPPORTED: The operation is not implemented, supported,
-// or enabled.
-#[allow(unused)]
-pub const ERR_NOT_SUPPORTED: zx_status_t = -2;
-
-// ERR_NO_RESOURCES: The system was not able to allocate some resource
-// needed for the operation.
-#[allow(unused)]
-pub const ERR_NO_RESOURCES: zx_status_t = -3;
-
-// ERR_NO_MEMORY: The system was not able to allocate memory needed
-// for the operation.
-#[allow(unused)]
-pub const ERR_NO_MEMORY: zx_status_t = -4;
-
-// ERR_CALL_FAILED: The second phase of zx_channel_call(; did not complete
-// successfully.
-#[allow(unused)]
-pub const ERR_CALL_FAILED: zx_status_t = -5;
-
-// ERR_INTERRUPTED_RETRY: The system call was interrupted, but should be
-// retried.  This should not be seen outside of the VDSO.
-#[allow(unused)]
-pub const ERR_INTERRUPTED_RETRY: zx_status_t = -6;
-
-// ======= Parameter errors =======
-// ERR_INVALID_ARGS: an argument is invalid, ex. null pointer
-#[allow(unused)]
-pub const ERR_INVALID_ARGS: zx_status_t = -10;
-
-// ERR_BAD_HANDLE: A specified handle value does not refer to a handle.
-#[allow(unused)]
-pub const ERR_BAD_HANDLE: zx_status_t = -11;
-
-// ERR_WRONG_TYPE: The subject of the operation is the wrong type to
-// perform the operation.
-// Example: Attempting a message_read on a thread handle.
-#[allow(unused)]
-pub const ERR_WRONG_TYPE: zx_status_t = -12;
-
-// ERR_BAD_SYSCALL: The specified syscall number is invalid.
-#[allow(unused)]
-pub const ERR_BAD_SYSCALL: zx_status_t = -13;
-
-// ERR_OUT_OF_RANGE: An argument is outside the valid range for this
-// operation.
-#[allow(unused)]
-pub const ERR_OUT_OF_RANGE: zx_status_t = -14;
-
-// ERR_BUFFER_TOO_SMALL: A caller provided buffer is too small for
-// this operation.
-#[allow(unused)]
-pub const ERR_BUFFER_TOO_SMALL: zx_status_t = -15;
-
-// ======= Precondition or state errors =======
-// ERR_BAD_STATE: operation failed because the current state of the
-// object does not allow it, or a precondition of the operation is
-// not satisfied
-#[allow(unused)]
-pub const ERR_BAD_STATE: zx_status_t = -20;
-
-// ERR_TIMED_OUT: The time limit for the operation elapsed before
-// the operation completed.
-#[allow(unused)]
-pub const ERR_TIMED_OUT: zx_status_t = -21;
-
-// ERR_SHOULD_WAIT: The operation cannot be performed currently but
-// potentially could succeed if the caller waits for a prerequisite
-// to be satisfied, for example waiting for a handle to be readable
-// or writable.
-// Example: Attempting to read from a message pipe that has no
-// messages waiting but has an open remote will return ERR_SHOULD_WAIT.
-// Attempting to read from a message pipe that has no messages waiting
-// and has a closed remote end will return ERR_REMOTE_CLOSED.
-#[allow(unused)]
-pub const ERR_SHOULD_WAIT: zx_status_t = -22;
-
-// ERR_CANCELED: The in-progress operation (e.g., a wait) has been
-// // canceled.
-#[allow(unused)]
-pub const ERR_CANCELED: zx_status_t = -23;
-
-// ERR_PEER_CLOSED: The operation failed because the remote end
-// of the subject of the operation was closed.
-#[allow(unused)]
-pub const ERR_PEER_CLOSED: zx_status_t = -24;
-
-// ERR_NOT_FOUND: The requested entity is not found.
-#[allow(unused)]
-pub const ERR_NOT_FOUND: zx_status_t = -25;
+impl FromRawFd for Handle {
+    #[inline]
+    unsafe fn from_raw_fd(fd: RawFd) -> Handle {
+        unsafe { Handle::from_raw(fd) }
+    }
+}
 
-// ERR_ALREADY_EXISTS: An object with the specified identifier
-// already exists.
-// Example: Attempting to create a file when a file already exists
-// with that name.
-#[allow(unused)]
-pub const ERR_ALREADY_EXISTS: zx_status_t = -26;
+impl From<Handle> for OwnedFd {
+    #[inline]
+    fn from(handle: Handle) -> OwnedFd {
+        unsafe { OwnedFd::from_raw_fd(handle.into_raw_fd()) }
+    }
+}
 
-// ERR_ALREADY_BOUND: The operation failed because the named entity
-// is already owned or controlled by another entity. The operation
-// could succeed later if the current owner releases the entity.
-#[allow(unused)]
-pub const ERR_ALREADY_BOUND: zx_status_t = -27;
+impl From<OwnedFd> for Handle {
+    #[inline]
+    fn from(fd: OwnedFd) -> Handle {
+        Handle {
+            fd: fd.into_raw_fd(),
+        }
+    }
+}
 
-// ERR_UNAVAILABLE: The subject of the operation is currently unable
-// to perform the operation.
-// Note: This is used when there's no direct way for the caller to
-// observe when the subject will be able to perform the operation
-// and should thus retry.
-#[allow(unused)]
-pub const ERR_UNAVAILABLE: zx_status_t = -28;
+// Process and thread primitives.
 
-// ======= Permission check errors =======
-// ERR_ACCESS_DENIED: The caller did not have permission to perform
-// the specified operation.
-#[allow(unused)]
-pub const ERR_ACCESS_DENIED: zx_status_t = -30;
+#[inline]
+pub fn getpid() -> libc::pid_t {
+    // Cannot fail.
+    unsafe { syscall(nr::SYS_getpid, &[]) as libc::pid_t }
+}
 
-// ======= Input-output errors =======
-// ERR_IO: Otherwise unspecified error occurred during I/O.
-#[allow(unused)]
-pub const ERR_IO: zx_status_t = -40;
+#[inline]
+pub fn sched_yield() {
+    // Cannot fail.
+    unsafe { syscall(nr::SYS_sched_yield, &[]) };
+}
 
-// ERR_REFUSED: The entity the I/O operation is being performed on
-// rejected the operation.
-// Example: an I2C device NAK'ing a transaction or a disk controller
-// rejecting an invalid command.
-#[allow(unused)]
-pub const ERR_IO_REFUSED: zx_status_t = -41;
+/// Fills `mask` with the CPU affinity mask of the thread `tid`, or of the
+/// calling thread if `tid` is 0. Returns the number of bytes written.
+pub fn sched_getaffinity(tid: libc::pid_t, mask: &mut [u8]) -> io::Result<usize> {
+    let args = [tid as usize, mask.len(), mask.as_mut_ptr().addr()];
+    cvt(unsafe { syscall(nr::SYS_sched_getaffinity, &args) })
+}
 
-// ERR_IO_DATA_INTEGRITY: The data in the operation failed an integrity
-// check and is possibly corrupted.
-// Example: CRC or Parity error.
-#[allow(unused)]
-pub const ERR_IO_DATA_INTEGRITY: zx_status_t = -42;
+/// Sets the CPU affinity mask of the thread `tid`, or of the calling thread
+/// if `tid` is 0.
+pub fn sched_setaffinity(tid: libc::pid_t, mask: &[u8]) -> io::Result<()> {
+    let args = [tid as usize, mask.len(), mask.as_ptr().addr()];
+    cvt(unsafe { syscall(nr::SYS_sched_setaffinity, &args) }).map(drop)
+}
 
-// ERR_IO_DATA_LOSS: The data in the operation is currently unavailable
-// and may be permanently lost.
-// Example: A disk block is irrecoverably damaged.
-#[allow(unused)]
-pub const ERR_IO_DATA_LOSS: zx_status_t = -43;
+pub fn kill(pid: libc::pid_t, sig: libc::c_int) -> io::Result<()> {
+    cvt(unsafe { syscall(nr::SYS_kill, &[pid as usize, sig as usize]) }).map(drop)
+}
 
-// Filesystem specific errors
-#[allow(unused)]
-pub const ERR_BAD_PATH: zx_status_t = -50;
-#[allow(unused)]
-pub const ERR_NOT_DIR: zx_status_t = -51;
-#[allow(unused)]
-pub const ERR_NOT_FILE: zx_status_t = -52;
-// ERR_FILE_BIG: A file exceeds a filesystem-specific size limit.
-#[allow(unused)]
-pub const ERR_FILE_BIG: zx_status_t = -53;
-// ERR_NO_SPACE: Filesystem or device space is exhausted.
-#[allow(unused)]
-pub const ERR_NO_SPACE: zx_status_t = -54;
+/// Opens a handle to the process `pid`. The handle has `FD_CLOEXEC` set.
+///
+/// The caller must make sure `pid` cannot be recycled while this runs, e.g.
+/// because it names an unreaped child.
+pub fn pidfd_open(pid: libc::pid_t) -> io::Result<Handle> {
+    let fd = cvt(unsafe { syscall(nr::SYS_pidfd_open, &[pid as usize, 0]) })?;
+    Ok(unsafe { Handle::from_raw(fd as RawFd) })
+}
@@ -0,0 +1,158 @@
+use super::{Errno, Handle, cvt, nr, syscall, syscall_errno};
+use crate::io;
+use crate::os::fd::{AsRawFd, FromRawFd, IntoRawFd, OwnedFd};
+
+fn pipe() -> (Handle, Handle) {
+    let mut fds = [0; 2];
+    assert_eq!(unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) }, 0);
+    unsafe { (Handle::from_raw(fds[0]), Handle::from_raw(fds[1])) }
+}
+
+fn is_open(fd: libc::c_int) -> bool {
+    unsafe { libc::fcntl(fd, libc::F_GETFD) != -1 }
+}
+
+#[test]
+fn errno_from_ret() {
+    assert_eq!(Errno::from_ret(0), Ok(0));
+    assert_eq!(Errno::from_ret(42), Ok(42));
+    assert_eq!(Errno::from_ret(-1), Err(Errno(libc::EPERM)));
+    assert_eq!(
+        Errno::from_ret(-(libc::ENOSYS as isize)),
+        Err(Errno(libc::ENOSYS))
+    );
+    assert_eq!(Errno::from_ret(-4095), Err(Errno(4095)));
+    // Values below the errno range are successful results, e.g. addresses
+    // returned by `mmap` in the upper half of the address space.
+    assert_eq!(Errno::from_ret(-4096), Ok(-4096isize as usize));
+    assert_eq!(Errno::from_ret(isize::MIN), Ok(isize::MIN as usize));
+}
+
+#[test]
+fn errno_into_io_error() {
+    let err = cvt(-(libc::ENOENT as isize)).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOENT));
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+    let err = io::Error::from(Errno(libc::EAGAIN));
+    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+
+    assert_eq!(cvt(7).unwrap(), 7);
+}
+
+#[test]
+fn raw_syscall() {
+    assert_eq!(super::getpid(), unsafe { libc::getpid() });
+    super::sched_yield();
+
+    let err = cvt(unsafe { syscall(100_000, &[]) }).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOSYS));
+
+    let ret = unsafe { syscall_errno(100_000, &[]) };
+    assert_eq!(ret, -1);
+    assert_eq!(
+        io::Error::last_os_error().raw_os_error(),
+        Some(libc::ENOSYS)
+    );
+
+    // Signal 0 only checks that the target exists.
+    super::kill(super::getpid(), 0).unwrap();
+    let err = super::kill(-1, -1).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+}
+
+#[test]
+fn handle_closes_on_drop() {
+    let (r, w) = pipe();
+    let (rfd, wfd) = (r.as_raw_fd(), w.as_raw_fd());
+    assert!(is_open(rfd) && is_open(wfd));
+    drop(r);
+    assert!(!is_open(rfd));
+    drop(w);
+    assert!(!is_open(wfd));
+}
+
+#[test]
+fn handle_raw_fd_conversions() {
+    let (r, w) = pipe();
+    let raw = r.into_raw_fd();
+    assert!(is_open(raw));
+    let owned = OwnedFd::from(unsafe { Handle::from_raw_fd(raw) });
+    assert_eq!(owned.as_raw_fd(), raw);
+    let back = Handle::from(owned);
+    assert_eq!(back.as_raw_fd(), raw);
+    drop(back);
+    assert!(!is_open(raw));
+    drop(w);
+}
+
+#[test]
+fn futex_wait_wake() {
+    use super::{FUTEX_PRIVATE_FLAG, FUTEX_WAIT_BITSET, FUTEX_WAKE};
+    use crate::sync::Arc;
+    use crate::sync::atomic::{AtomicU32, Ordering};
+    use crate::thread;
+
+    fn futex(word: &AtomicU32, op: libc::c_int, val: u32) -> io::Result<usize> {
+        let op = (op | FUTEX_PRIVATE_FLAG) as usize;
+        // No timeout, and a full bitmask so that FUTEX_WAIT_BITSET behaves
+        // like FUTEX_WAIT.
+        let args = [word.as_ptr().addr(), op, val as usize, 0, 0, !0u32 as usize];
+        cvt(unsafe { syscall(nr::SYS_futex, &args) })
+    }
+
+    // Waiting on a value that doesn't match returns immediately.
+    let word = Arc::new(AtomicU32::new(1));
+    let err = futex(&word, FUTEX_WAIT_BITSET, 0).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EAGAIN));
+
+    let waiter = {
+        let word = word.clone();
+        thread::spawn(move || {
+            while word.load(Ordering::Acquire) == 1 {
+                let _ = futex(&word, FUTEX_WAIT_BITSET, 1);
+            }
+        })
+    };
+    thread::sleep(crate::time::Duration::from_millis(10));
+    word.store(2, Ordering::Release);
+    futex(&word, FUTEX_WAKE, 1).unwrap();
+    waiter.join().unwrap();
+}
+
+#[test]
+fn sched_affinity() {
+    let mut mask = [0u8; 128];
+    let len = super::sched_getaffinity(0, &mut mask).unwrap();
+    assert!(len > 0 && len <= mask.len());
+    let count: u32 = mask[..len].iter().map(|b| b.count_ones()).sum();
+
+    let mut set: libc::cpu_set_t = unsafe { crate::mem::zeroed() };
+    assert_eq!(
+        unsafe { libc::sched_getaffinity(0, size_of_val(&set), &mut set) },
+        0
+    );
+    assert_eq!(count, unsafe { libc::CPU_COUNT(&set) } as u32);
+
+    // Setting the mask we already have is always allowed.
+    super::sched_setaffinity(0, &mask[..len]).unwrap();
+
+    // A buffer smaller than the kernel's mask is rejected.
+    let err = super::sched_getaffinity(0, &mut [0u8; 0]).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+}
+
+#[test]
+fn pidfd_open() {
+    let handle = match super::pidfd_open(super::getpid()) {
+        Ok(handle) => handle,
+        // Hosts whose kernels predate `pidfd_open`.
+        Err(e) if e.raw_os_error() == Some(libc::ENOSYS) => return,
+        Err(e) => panic!("pidfd_open failed: {e}"),
+    };
+    let flags = unsafe { libc::fcntl(handle.as_raw_fd(), libc::F_GETFD) };
+    assert!(flags & libc::FD_CLOEXEC != 0);
+
+    let err = super::pidfd_open(-1).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+}
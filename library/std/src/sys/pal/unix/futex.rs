@@ -1,4 +1,5 @@
 #![cfg(any(
+    target_os = "aether",
     target_os = "linux",
     target_os = "android",
     all(target_os = "emscripten", target_feature = "atomics"),
@@ -115,6 +116,14 @@ pub fn futex_wake_all(futex: &Atomic<u32>) {
     }
 }
 
+// aether implements the Linux futex interface, but its libc has no `syscall`
+// wrapper, so its operations go straight to the kernel. Linux hosts build them
+// for their unit tests.
+#[cfg(any(target_os = "aether", all(test, target_os = "linux", target_arch = "x86_64")))]
+mod aether;
+#[cfg(target_os = "aether")]
+pub use aether::{futex_wait, futex_wake, futex_wake_all};
+
 // FreeBSD doesn't tell us how many threads are woken up, so this always returns false.
 #[cfg(target_os = "freebsd")]
 pub fn futex_wake(futex: &Atomic<u32>) -> bool {
@@ -0,0 +1,66 @@
+use super::{Atomic, Duration};
+use crate::ptr::null;
+use crate::sync::atomic::Ordering::Relaxed;
+use crate::sys::pal::aether::{
+    Errno, FUTEX_PRIVATE_FLAG, FUTEX_WAIT_BITSET, FUTEX_WAKE, nr, syscall,
+};
+use crate::sys::pal::time::Timespec;
+
+#[cfg(test)]
+mod tests;
+
+/// Waits for a `futex_wake` operation to wake us.
+///
+/// Returns directly if the futex doesn't hold the expected value.
+///
+/// Returns false on timeout, and true in all other cases.
+pub fn futex_wait(futex: &Atomic<u32>, expected: u32, timeout: Option<Duration>) -> bool {
+    // Calculate the timeout as an absolute timespec.
+    //
+    // Overflows are rounded up to an infinite timeout (None).
+    let timespec = timeout
+        .and_then(|d| Timespec::now(libc::CLOCK_MONOTONIC).checked_add_duration(&d))
+        .and_then(|t| t.to_timespec());
+
+    loop {
+        // No need to wait if the value already changed.
+        if futex.load(Relaxed) != expected {
+            return true;
+        }
+
+        // Like on Linux, FUTEX_WAIT_BITSET is used for its absolute timeout.
+        let args = [
+            futex.as_ptr().addr(),
+            (FUTEX_WAIT_BITSET | FUTEX_PRIVATE_FLAG) as usize,
+            expected as usize,
+            timespec
+                .as_ref()
+                .map_or(null(), |t| t as *const libc::timespec)
+                .addr(),
+            0,              // This argument is unused for FUTEX_WAIT_BITSET.
+            !0u32 as usize, // A full bitmask, to make it behave like a regular FUTEX_WAIT.
+        ];
+        match Errno::from_ret(unsafe { syscall(nr::SYS_futex, &args) }) {
+            Err(Errno(libc::ETIMEDOUT)) => return false,
+            Err(Errno(libc::EINTR)) => continue,
+            _ => return true,
+        }
+    }
+}
+
+/// Wakes up one thread that's blocked on `futex_wait` on this futex.
+///
+/// Returns true if this actually woke up such a thread,
+/// or false if no thread was waiting on this futex.
+pub fn futex_wake(futex: &Atomic<u32>) -> bool {
+    let op = FUTEX_WAKE | FUTEX_PRIVATE_FLAG;
+    let args = [futex.as_ptr().addr(), op as usize, 1];
+    matches!(Errno::from_ret(unsafe { syscall(nr::SYS_futex, &args) }), Ok(n) if n > 0)
+}
+
+/// Wakes up all threads that are waiting on `futex_wait` on this futex.
+pub fn futex_wake_all(futex: &Atomic<u32>) {
+    let op = FUTEX_WAKE | FUTEX_PRIVATE_FLAG;
+    let args = [futex.as_ptr().addr(), op as usize, i32::MAX as usize];
+    unsafe { syscall(nr::SYS_futex, &args) };
+}
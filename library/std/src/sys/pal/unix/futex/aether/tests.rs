@@ -0,0 +1,76 @@
+use super::{futex_wait, futex_wake, futex_wake_all};
+use crate::sync::Arc;
+use crate::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use crate::thread;
+use crate::time::{Duration, Instant};
+
+/// Spawns a thread that waits on `futex` for as long as it holds 0, and
+/// counts its wakeups in `woken`.
+fn waiter(futex: &Arc<AtomicU32>, woken: &Arc<AtomicUsize>) -> thread::JoinHandle<()> {
+    let (futex, woken) = (futex.clone(), woken.clone());
+    thread::spawn(move || {
+        while futex.load(Ordering::Acquire) == 0 {
+            futex_wait(&futex, 0, None);
+        }
+        woken.fetch_add(1, Ordering::Relaxed);
+    })
+}
+
+#[test]
+fn wait_returns_if_value_differs() {
+    let futex = AtomicU32::new(1);
+    assert!(futex_wait(&futex, 0, None));
+    assert!(futex_wait(&futex, 0, Some(Duration::from_secs(60))));
+}
+
+#[test]
+fn wait_times_out() {
+    let futex = AtomicU32::new(0);
+    let timeout = Duration::from_millis(20);
+    let start = Instant::now();
+    assert!(!futex_wait(&futex, 0, Some(timeout)));
+    assert!(start.elapsed() >= timeout);
+
+    // Overflowing timeouts are treated as infinite, so a zero timeout is the
+    // only other one that can be tested here.
+    assert!(!futex_wait(&futex, 0, Some(Duration::ZERO)));
+}
+
+#[test]
+fn wake_without_waiters() {
+    let futex = AtomicU32::new(0);
+    assert!(!futex_wake(&futex));
+    futex_wake_all(&futex);
+}
+
+#[test]
+fn wake_one() {
+    let futex = Arc::new(AtomicU32::new(0));
+    let woken = Arc::new(AtomicUsize::new(0));
+    let handle = waiter(&futex, &woken);
+
+    // A wakeup is only reported once the thread is blocked in the kernel. The
+    // thread goes back to waiting after this one, since the value is still 0.
+    while !futex_wake(&futex) {
+        thread::sleep(Duration::from_millis(1));
+    }
+    futex.store(1, Ordering::Release);
+    futex_wake(&futex);
+    handle.join().unwrap();
+    assert_eq!(woken.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn wake_all() {
+    let futex = Arc::new(AtomicU32::new(0));
+    let woken = Arc::new(AtomicUsize::new(0));
+    let handles: Vec<_> = (0..4).map(|_| waiter(&futex, &woken)).collect();
+
+    thread::sleep(Duration::from_millis(20));
+    futex.store(1, Ordering::Release);
+    futex_wake_all(&futex);
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(woken.load(Ordering::Relaxed), 4);
+}
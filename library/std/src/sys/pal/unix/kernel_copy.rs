@@ -42,7 +42,11 @@
 //!   progress, they can hit a performance cliff.
 //! * complexity
 
-#[cfg(not(any(all(target_os = "linux", target_env = "gnu"), target_os = "hurd")))]
+#[cfg(not(any(
+    all(target_os = "linux", target_env = "gnu"),
+    target_os = "hurd",
+    target_os = "aether"
+)))]
 use libc::sendfile as sendfile64;
 #[cfg(any(all(target_os = "linux", target_env = "gnu"), target_os = "hurd"))]
 use libc::sendfile64;
@@ -740,7 +744,9 @@ fn sendfile_splice(mode: SpliceMode, reader: RawFd, writer: RawFd, len: u64) ->
 
     // Android builds use feature level 14, but the libc wrapper for splice is
     // gated on feature level 21+, so we have to invoke the syscall directly.
-    #[cfg(target_os = "android")]
+    // aether's libc doesn't necessarily wrap either call, so `syscall!` falls
+    // back to the raw system call there as well.
+    #[cfg(any(target_os = "android", target_os = "aether"))]
     syscall!(
         fn splice(
             srcfd: libc::c_int,
@@ -752,8 +758,20 @@ fn sendfile_splice(mode: SpliceMode, reader: RawFd, writer: RawFd, len: u64) ->
         ) -> libc::ssize_t;
     );
 
+    #[cfg(target_os = "aether")]
+    syscall!(
+        fn sendfile(
+            out_fd: libc::c_int,
+            in_fd: libc::c_int,
+            offset: *mut libc::off_t,
+            count: libc::size_t,
+        ) -> libc::ssize_t;
+    );
+
     #[cfg(target_os = "linux")]
     use libc::splice;
+    #[cfg(target_os = "aether")]
+    use sendfile as sendfile64;
 
     match mode {
         SpliceMode::Sendfile if !HAS_SENDFILE.load(Ordering::Relaxed) => {
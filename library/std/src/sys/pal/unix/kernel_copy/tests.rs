@@ -140,6 +140,53 @@ fn dont_splice_pipes_from_files() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn sendfile_and_splice() -> Result<()> {
+    // Exercise both calls directly, since `io::copy` silently falls back to a
+    // read-write loop if they fail.
+    use super::{CopyResult, SpliceMode, sendfile_splice};
+    use crate::os::unix::net::UnixStream;
+    use crate::process::{ChildStdin, ChildStdout};
+    use crate::sys_common::FromInner;
+
+    const DATA: &[u8] = b"moved by the kernel";
+    let len = DATA.len() as u64;
+
+    let tmp_path = tmpdir();
+    let path = tmp_path.join("sendfile-source");
+    crate::fs::write(&path, DATA)?;
+    let file = crate::fs::File::open(&path)?;
+
+    let (read_end, write_end) = crate::sys::pipe::anon_pipe()?;
+    let read_end = ChildStdout::from_inner(read_end);
+    let write_end = ChildStdin::from_inner(write_end);
+    let (mut rx, tx) = UnixStream::pair()?;
+
+    // file -> pipe
+    let result = sendfile_splice(
+        SpliceMode::Sendfile,
+        file.as_raw_fd(),
+        write_end.as_raw_fd(),
+        len,
+    );
+    assert!(matches!(result, CopyResult::Ended(n) if n == len));
+
+    // pipe -> socket
+    let result = sendfile_splice(
+        SpliceMode::Splice,
+        read_end.as_raw_fd(),
+        tx.as_raw_fd(),
+        len,
+    );
+    assert!(matches!(result, CopyResult::Ended(n) if n == len));
+
+    let mut buf = vec![0; DATA.len()];
+    rx.read_exact(&mut buf)?;
+    assert_eq!(buf, DATA);
+
+    Ok(())
+}
+
 #[bench]
 fn bench_file_to_file_copy(b: &mut test::Bencher) {
     const BYTES: usize = 128 * 1024;
@@ -232,7 +279,7 @@ fn bench_file_to_uds_copy(b: &mut test::Bencher) {
     });
 }
 
-#[cfg(any(target_os = "linux", target_os = "android"))]
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "aether"))]
 #[bench]
 fn bench_socket_pipe_socket_copy(b: &mut test::Bencher) {
     use super::CopyResult;
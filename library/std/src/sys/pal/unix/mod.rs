@@ -6,12 +6,15 @@ use crate::io::ErrorKind;
 #[macro_use]
 pub mod weak;
 
-#[cfg(target_os = "aether")]
+#[cfg(any(
+    target_os = "aether",
+    all(test, target_os = "linux", target_arch = "x86_64")
+))]
 pub mod aether;
 #[cfg(target_os = "fuchsia")]
 pub mod fuchsia;
 pub mod futex;
-#[cfg(any(target_os = "linux", target_os = "android"))]
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "aether"))]
 pub mod kernel_copy;
 #[cfg(target_os = "linux")]
 pub mod linux;
@@ -45,7 +48,6 @@ pub unsafe fn init(argc: isize, argv: *const *const u8, sigpipe: u8) {
     // Hence, we set SIGPIPE to ignore when the program starts up in order
     // to prevent this problem. Use `-Zon-broken-pipe=...` to alter this
     // behavior.
-    #[cfg(not(target_os = "aether"))]
     reset_sigpipe(sigpipe);
 
     stack_overflow::init();
@@ -65,7 +67,6 @@ pub unsafe fn init(argc: isize, argv: *const *const u8, sigpipe: u8) {
         // fast path with a single syscall for systems with poll()
         #[cfg(not(any(
             miri,
-            target_os = "aether",
             target_os = "emscripten",
             target_os = "fuchsia",
             target_os = "vxworks",
@@ -105,9 +106,10 @@ pub unsafe fn init(argc: isize, argv: *const *const u8, sigpipe: u8) {
             while libc::poll(pfds.as_mut_ptr(), 3, 0) == -1 {
                 match errno() {
                     libc::EINTR => continue,
-                    #[cfg(target_vendor = "unikraft")]
+                    #[cfg(any(target_vendor = "unikraft", target_os = "aether"))]
                     libc::ENOSYS => {
-                        // Not all configurations of Unikraft enable `LIBPOSIX_EVENT`.
+                        // Not all configurations of Unikraft enable `LIBPOSIX_EVENT`,
+                        // and aether kernels can be built without poll support.
                         break 'poll;
                     }
                     libc::EINVAL | libc::EAGAIN | libc::ENOMEM => {
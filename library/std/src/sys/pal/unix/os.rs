@@ -454,7 +454,6 @@ pub fn current_exe() -> io::Result<PathBuf> {
 }
 
 #[cfg(any(
-    target_os = "aether",
     target_os = "linux",
     target_os = "cygwin",
     target_os = "hurd",
@@ -472,6 +471,78 @@ pub fn current_exe() -> io::Result<PathBuf> {
     }
 }
 
+#[cfg(target_os = "aether")]
+pub fn current_exe() -> io::Result<PathBuf> {
+    #[cfg(test)]
+    use realstd::env;
+
+    #[cfg(not(test))]
+    use crate::env;
+
+    // Minimal images don't always mount /proc, so fall back to what the
+    // kernel recorded at exec time, and finally to argv[0].
+    crate::fs::read_link("/proc/self/exe")
+        .or_else(|_| exe_from_execfn())
+        .or_else(|_| {
+            let argv0 = env::args_os().next().ok_or(io::const_error!(
+                io::ErrorKind::NotFound,
+                "no /proc/self/exe, AT_EXECFN or argv[0] available",
+            ))?;
+            exe_from_argv0(&argv0, env::var_os("PATH").as_deref())
+        })
+}
+
+/// Infers the path of the running executable from `argv[0]`, following the
+/// lookup the shell that started us would have done with `search_path` as
+/// `PATH`.
+#[cfg(any(target_os = "aether", all(test, target_os = "linux")))]
+fn exe_from_argv0(argv0: &OsStr, search_path: Option<&OsStr>) -> io::Result<PathBuf> {
+    use crate::io::ErrorKind;
+
+    let path = path::Path::new(argv0);
+    if path.is_absolute() {
+        return path.canonicalize();
+    }
+    // Search PWD to infer current_exe.
+    if argv0.as_bytes().contains(&b'/') {
+        return getcwd().map(|cwd| cwd.join(path))?.canonicalize();
+    }
+    // Search PATH to infer current_exe.
+    if let Some(p) = search_path {
+        for search_path in split_paths(p) {
+            let pb = search_path.join(path);
+            if pb.is_file()
+                && let Ok(metadata) = crate::fs::metadata(&pb)
+                && metadata.permissions().mode() & 0o111 != 0
+            {
+                return pb.canonicalize();
+            }
+        }
+    }
+    Err(io::const_error!(
+        ErrorKind::NotFound,
+        "an executable path was not found"
+    ))
+}
+
+/// Resolves the pathname the executable was started with, as recorded by the
+/// kernel in the `AT_EXECFN` auxiliary vector entry.
+///
+/// A relative pathname is resolved against the current working directory,
+/// which is only correct if it hasn't changed since the exec.
+#[cfg(any(target_os = "aether", all(test, target_os = "linux")))]
+fn exe_from_execfn() -> io::Result<PathBuf> {
+    let execfn = unsafe { libc::getauxval(libc::AT_EXECFN) } as *const c_char;
+    if execfn.is_null() {
+        return Err(io::const_error!(
+            io::ErrorKind::NotFound,
+            "no AT_EXECFN entry in the auxiliary vector",
+        ));
+    }
+    let execfn = unsafe { CStr::from_ptr(execfn) };
+    path::Path::new(OsStr::from_bytes(execfn.to_bytes())).canonicalize()
+}
+
 #[cfg(target_os = "nto")]
 pub fn current_exe() -> io::Result<PathBuf> {
     let mut e = crate::fs::read("/proc/self/exefile")?;
@@ -766,10 +837,16 @@ pub fn exit(code: i32) -> ! {
     unsafe { libc::exit(code as c_int) }
 }
 
+#[cfg(not(target_os = "aether"))]
 pub fn getpid() -> u32 {
     unsafe { libc::getpid() as u32 }
 }
 
+#[cfg(target_os = "aether")]
+pub fn getpid() -> u32 {
+    super::aether::getpid() as u32
+}
+
 pub fn getppid() -> u32 {
     unsafe { libc::getppid() as u32 }
 }
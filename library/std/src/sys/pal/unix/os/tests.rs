@@ -46,3 +46,60 @@ fn test_confstr() {
         assert!(super::confstr(hopefully_invalid, hint).is_err());
     }
 }
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_exe_from_execfn() {
+    // The test harness was started through a path naming this executable.
+    let exe = realstd::env::current_exe().unwrap();
+    assert_eq!(
+        super::exe_from_execfn().unwrap(),
+        exe.canonicalize().unwrap()
+    );
+}
+
+#[test]
+#[cfg(all(target_os = "linux", not(miri)))]
+fn test_exe_from_argv0() {
+    use crate::ffi::OsStr;
+    use crate::fs;
+    use crate::io::ErrorKind;
+    use crate::os::unix::fs::PermissionsExt;
+    use crate::test_helpers::tmpdir;
+
+    let tmp = tmpdir();
+    let first = tmp.join("first");
+    let second = tmp.join("second");
+    fs::create_dir(&first).unwrap();
+    fs::create_dir(&second).unwrap();
+
+    // Only the executable in `second` is a match for a `PATH` search.
+    fs::write(first.join("prog"), b"").unwrap();
+    fs::write(second.join("prog"), b"").unwrap();
+    fs::set_permissions(second.join("prog"), fs::Permissions::from_mode(0o755)).unwrap();
+    let prog = second.join("prog").canonicalize().unwrap();
+
+    // Absolute paths are used as they are.
+    let argv0 = prog.as_os_str();
+    assert_eq!(super::exe_from_argv0(argv0, None).unwrap(), prog);
+
+    // Paths containing a slash are relative to the working directory.
+    let cwd = super::getcwd().unwrap();
+    let relative = crate::iter::repeat_n("..", cwd.components().count())
+        .collect::<crate::path::PathBuf>()
+        .join(prog.strip_prefix("/").unwrap());
+    assert_eq!(
+        super::exe_from_argv0(relative.as_os_str(), None).unwrap(),
+        prog
+    );
+
+    // Bare names are looked up in `PATH`.
+    let search_path = crate::env::join_paths([&first, &second]).unwrap();
+    let found = super::exe_from_argv0(OsStr::new("prog"), Some(&search_path)).unwrap();
+    assert_eq!(found, prog);
+
+    let err = super::exe_from_argv0(OsStr::new("prog"), Some(first.as_os_str())).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+    let err = super::exe_from_argv0(OsStr::new("prog"), None).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+}
@@ -28,6 +28,7 @@ impl Drop for Handler {
 }
 
 #[cfg(any(
+    target_os = "aether",
     target_os = "linux",
     target_os = "freebsd",
     target_os = "hurd",
@@ -174,6 +175,7 @@ mod imp {
             target_os = "netbsd",
             target_os = "linux",
             target_os = "dragonfly",
+            target_os = "aether",
         ))]
         let flags = MAP_PRIVATE | MAP_ANON | libc::MAP_STACK;
         #[cfg(not(any(
@@ -181,6 +183,7 @@ mod imp {
             target_os = "netbsd",
             target_os = "linux",
             target_os = "dragonfly",
+            target_os = "aether",
         )))]
         let flags = MAP_PRIVATE | MAP_ANON;
 
@@ -327,6 +330,7 @@ mod imp {
     }
 
     #[cfg(any(
+        target_os = "aether",
         target_os = "android",
         target_os = "freebsd",
         target_os = "netbsd",
@@ -389,6 +393,8 @@ mod imp {
                 install_main_guard_linux(page_size)
             } else if cfg!(all(target_os = "linux", target_env = "musl")) {
                 install_main_guard_linux_musl(page_size)
+            } else if cfg!(target_os = "aether") {
+                install_main_guard_aether(page_size)
             } else if cfg!(target_os = "freebsd") {
                 install_main_guard_freebsd(page_size)
             } else if cfg!(any(target_os = "netbsd", target_os = "openbsd")) {
@@ -425,6 +431,42 @@ mod imp {
         None
     }
 
+    #[cfg(target_os = "aether")]
+    #[forbid(unsafe_op_in_unsafe_fn)]
+    unsafe fn install_main_guard_aether(page_size: usize) -> Option<Range<usize>> {
+        // aether's libc is derived from musl, so pthread_attr_getstack has
+        // the same problem as described in `install_main_guard_linux_musl`.
+        // Instead, find the limit the way the kernel computes it: the
+        // initial stack mapping ends right above the executable's path, which
+        // the kernel copies to the very top of it, and it may grow down to
+        // RLIMIT_STACK below that end. Like on Linux, we only note where the
+        // kernel starts faulting and trust its own stack guard.
+        let execfn = unsafe { libc::getauxval(libc::AT_EXECFN) } as *const libc::c_char;
+        if execfn.is_null() {
+            return None;
+        }
+        let stack_end =
+            (execfn.addr() + unsafe { libc::strlen(execfn) } + 1).next_multiple_of(page_size);
+
+        let mut limit = mem::MaybeUninit::<libc::rlimit>::uninit();
+        if unsafe { libc::getrlimit(libc::RLIMIT_STACK, limit.as_mut_ptr()) } != 0 {
+            return None;
+        }
+        let limit = unsafe { limit.assume_init() }.rlim_cur;
+        if limit == libc::RLIM_INFINITY {
+            return None;
+        }
+        let stackaddr = stack_end
+            .checked_sub(limit as usize)?
+            .next_multiple_of(page_size);
+        Some(stackaddr - page_size..stackaddr)
+    }
+
+    #[cfg(not(target_os = "aether"))]
+    unsafe fn install_main_guard_aether(_page_size: usize) -> Option<Range<usize>> {
+        None
+    }
+
     #[forbid(unsafe_op_in_unsafe_fn)]
     unsafe fn install_main_guard_freebsd(page_size: usize) -> Option<Range<usize>> {
         // FreeBSD's stack autogrows, and optionally includes a guard page
@@ -542,6 +584,7 @@ mod imp {
     }
 
     #[cfg(any(
+        target_os = "aether",
         target_os = "android",
         target_os = "freebsd",
         target_os = "hurd",
@@ -570,10 +613,14 @@ mod imp {
                 0
             );
             if guardsize == 0 {
-                if cfg!(all(target_os = "linux", target_env = "musl")) {
+                if cfg!(any(
+                    all(target_os = "linux", target_env = "musl"),
+                    target_os = "aether"
+                )) {
                     // musl versions before 1.1.19 always reported guard
                     // size obtained from pthread_attr_get_np as zero.
-                    // Use page size as a fallback.
+                    // Use page size as a fallback. aether's libc is derived
+                    // from musl, so it gets the same treatment.
                     guardsize = PAGE_SIZE.load(Ordering::Relaxed);
                 } else {
                     panic!("there is no guard page");
@@ -593,7 +640,10 @@ mod imp {
                 target_os = "hurd"
             )) {
                 Some(stackaddr - guardsize..stackaddr)
-            } else if cfg!(all(target_os = "linux", target_env = "musl")) {
+            } else if cfg!(any(
+                all(target_os = "linux", target_env = "musl"),
+                target_os = "aether"
+            )) {
                 Some(stackaddr - guardsize..stackaddr)
             } else if cfg!(all(
                 target_os = "linux",
@@ -626,6 +676,7 @@ mod imp {
 // is shipped with the OS):
 // <https://github.com/apple/swift/blob/swift-5.10-RELEASE/stdlib/public/runtime/CrashHandlerMacOS.cpp>
 #[cfg(not(any(
+    target_os = "aether",
     target_os = "linux",
     target_os = "freebsd",
     target_os = "hurd",
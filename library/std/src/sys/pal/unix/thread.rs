@@ -1,13 +1,24 @@
 use crate::ffi::CStr;
+#[cfg(target_os = "aether")]
+use crate::ffi::CString;
 use crate::mem::{self, ManuallyDrop};
 use crate::num::NonZero;
 #[cfg(all(target_os = "linux", target_env = "gnu"))]
 use crate::sys::weak::dlsym;
-#[cfg(any(target_os = "solaris", target_os = "illumos", target_os = "nto",))]
+#[cfg(any(
+    target_os = "aether",
+    target_os = "solaris",
+    target_os = "illumos",
+    target_os = "nto",
+))]
 use crate::sys::weak::weak;
 use crate::sys::{os, stack_overflow};
 use crate::time::Duration;
 use crate::{cmp, io, ptr};
+
+#[cfg(test)]
+mod tests;
+
 #[cfg(not(any(
     target_os = "l4re",
     target_os = "vxworks",
@@ -22,6 +33,10 @@ pub const DEFAULT_MIN_STACK_SIZE: usize = 256 * 1024;
 #[cfg(any(target_os = "espidf", target_os = "nuttx"))]
 pub const DEFAULT_MIN_STACK_SIZE: usize = 0; // 0 indicates that the stack size configured in the ESP-IDF/NuttX menuconfig system should be used
 
+/// The size of the kernel's buffer for a thread name, including the nul.
+#[cfg(any(target_os = "linux", target_os = "cygwin", target_os = "aether"))]
+const TASK_COMM_LEN: usize = 16;
+
 #[cfg(target_os = "fuchsia")]
 mod zircon {
     type zx_handle_t = u32;
@@ -108,6 +123,12 @@ impl Thread {
             Ok(Thread { id: native })
         };
 
+        // The signature has to be exactly the `void *(*)(void *)` that
+        // `pthread_create` expects: libc built with KCFI checks the type id
+        // of the start routine before calling it. `c_void` is the one Rust
+        // type that KCFI encodes as C's `void`, so both the argument and the
+        // return value must use it; a `*mut u8` would encode as `char *` and
+        // the check would fail.
         extern "C" fn thread_start(main: *mut libc::c_void) -> *mut libc::c_void {
             unsafe {
                 // Next, set up our stack overflow handler which may get triggered if we run
@@ -120,11 +141,17 @@ impl Thread {
         }
     }
 
+    #[cfg(not(target_os = "aether"))]
     pub fn yield_now() {
         let ret = unsafe { libc::sched_yield() };
         debug_assert_eq!(ret, 0);
     }
 
+    #[cfg(target_os = "aether")]
+    pub fn yield_now() {
+        crate::sys::pal::aether::sched_yield();
+    }
+
     #[cfg(target_os = "android")]
     pub fn set_name(name: &CStr) {
         const PR_SET_NAME: libc::c_int = 15;
@@ -154,8 +181,11 @@ impl Thread {
             cfg_if::cfg_if! {
                 if #[cfg(any(target_os = "linux", target_os = "cygwin"))] {
                     // Linux and Cygwin limits the allowed length of the name.
-                    const TASK_COMM_LEN: usize = 16;
                     let name = truncate_cstr::<{ TASK_COMM_LEN }>(name);
+                } else if #[cfg(target_os = "aether")] {
+                    // aether limits the length too. Keep the name valid UTF-8 so that
+                    // `get_name` can still report it.
+                    let name = truncate_cstr_utf8::<{ TASK_COMM_LEN }>(name);
                 } else {
                     // FreeBSD, DragonFly BSD and NuttX do not enforce length limits.
                 }
@@ -168,6 +198,22 @@ impl Thread {
         }
     }
 
+    #[cfg(target_os = "aether")]
+    pub fn get_name() -> Option<CString> {
+        let mut name = vec![0u8; TASK_COMM_LEN];
+        let res = unsafe {
+            libc::pthread_getname_np(libc::pthread_self(), name.as_mut_ptr().cast(), name.len())
+        };
+        if res != 0 {
+            return None;
+        }
+        name.truncate(name.iter().position(|&c| c == 0)?);
+        if name.is_empty() {
+            return None;
+        }
+        CString::new(name).ok()
+    }
+
     #[cfg(target_os = "openbsd")]
     pub fn set_name(name: &CStr) {
         unsafe {
@@ -346,6 +392,55 @@ impl Thread {
     pub fn into_id(self) -> libc::pthread_t {
         ManuallyDrop::new(self).id
     }
+
+    /// Returns the kernel id of the thread.
+    #[cfg(target_os = "aether")]
+    pub fn native_id(&self) -> io::Result<libc::pid_t> {
+        weak!(
+            fn pthread_gettid_np(thread: libc::pthread_t) -> libc::pid_t;
+        );
+
+        match pthread_gettid_np.get() {
+            // Returns -1 once the thread has exited.
+            Some(f) => match unsafe { f(self.id) } {
+                -1 => Err(io::const_error!(
+                    io::ErrorKind::NotFound,
+                    "the thread has exited"
+                )),
+                tid => Ok(tid),
+            },
+            None => Err(io::const_error!(
+                io::ErrorKind::Unsupported,
+                "pthread_gettid_np is not available",
+            )),
+        }
+    }
+
+    /// Returns the CPUs the thread may run on.
+    #[cfg(target_os = "aether")]
+    pub fn affinity(&self) -> io::Result<Vec<usize>> {
+        let mut mask = [0u8; 128];
+        let len = crate::sys::pal::aether::sched_getaffinity(self.native_id()?, &mut mask)?;
+        Ok((0..len * 8)
+            .filter(|&cpu| mask[cpu / 8] & (1 << (cpu % 8)) != 0)
+            .collect())
+    }
+
+    /// Restricts the thread to the given CPUs.
+    #[cfg(target_os = "aether")]
+    pub fn set_affinity(&self, cpus: &[usize]) -> io::Result<()> {
+        let mut mask = [0u8; 128];
+        for &cpu in cpus {
+            let Some(byte) = mask.get_mut(cpu / 8) else {
+                return Err(io::const_error!(
+                    io::ErrorKind::InvalidInput,
+                    "CPU index out of range"
+                ));
+            };
+            *byte |= 1 << (cpu % 8);
+        }
+        crate::sys::pal::aether::sched_setaffinity(self.native_id()?, &mask)
+    }
 }
 
 impl Drop for Thread {
@@ -372,9 +467,26 @@ fn truncate_cstr<const MAX_WITH_NUL: usize>(cstr: &CStr) -> [libc::c_char; MAX_W
     result
 }
 
+/// Like `truncate_cstr`, but backs off to a character boundary so that a
+/// UTF-8 name doesn't get cut in the middle of a character.
+#[cfg(any(target_os = "aether", all(test, target_os = "linux")))]
+fn truncate_cstr_utf8<const MAX_WITH_NUL: usize>(cstr: &CStr) -> [libc::c_char; MAX_WITH_NUL] {
+    let bytes = cstr.to_bytes();
+    let mut len = bytes.len().min(MAX_WITH_NUL - 1);
+    while len < bytes.len() && len > 0 && bytes[len] & 0xc0 == 0x80 {
+        len -= 1;
+    }
+    let mut result = [0; MAX_WITH_NUL];
+    for (src, dst) in bytes[..len].iter().zip(&mut result) {
+        *dst = *src as libc::c_char;
+    }
+    result
+}
+
 pub fn available_parallelism() -> io::Result<NonZero<usize>> {
     cfg_if::cfg_if! {
         if #[cfg(any(
+            target_os = "aether",
             target_os = "android",
             target_os = "emscripten",
             target_os = "fuchsia",
@@ -407,6 +519,20 @@ pub fn available_parallelism() -> io::Result<NonZero<usize>> {
                     }
                 }
             }
+
+            #[cfg(target_os = "aether")]
+            {
+                quota = cgroups::quota().max(1);
+                // Large enough for 1024 CPUs, like glibc's `cpu_set_t`.
+                let mut mask = [0u8; 128];
+                if let Ok(len) = crate::sys::pal::aether::sched_getaffinity(0, &mut mask) {
+                    let count: usize = mask[..len].iter().map(|b| b.count_ones() as usize).sum();
+                    if let Some(count) = NonZero::new(count.min(quota)) {
+                        return Ok(count)
+                    }
+                }
+            }
+
             match unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) } {
                 -1 => Err(io::Error::last_os_error()),
                 0 => Err(io::Error::UNKNOWN_THREAD_COUNT),
@@ -546,7 +672,7 @@ pub fn available_parallelism() -> io::Result<NonZero<usize>> {
     }
 }
 
-#[cfg(any(target_os = "android", target_os = "linux"))]
+#[cfg(any(target_os = "aether", target_os = "android", target_os = "linux"))]
 mod cgroups {
     //! Currently not covered
     //! * cgroup v2 in non-standard mountpoints
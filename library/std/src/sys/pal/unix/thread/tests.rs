@@ -0,0 +1,34 @@
+#[test]
+#[cfg(any(target_os = "linux", target_os = "aether"))]
+fn truncate_cstr_utf8_keeps_characters_intact() {
+    use crate::ffi::CStr;
+
+    fn truncated<const N: usize>(name: &CStr) -> Vec<u8> {
+        super::truncate_cstr_utf8::<N>(name)
+            .iter()
+            .take_while(|&&c| c != 0)
+            .map(|&c| c as u8)
+            .collect()
+    }
+
+    assert_eq!(truncated::<16>(c"short"), b"short");
+    assert_eq!(truncated::<16>(c"fifteen bytes!!"), b"fifteen bytes!!");
+    assert_eq!(truncated::<16>(c"sixteen bytes!!!"), b"sixteen bytes!!");
+    // Cutting "é" after its first byte would leave invalid UTF-8.
+    assert_eq!(truncated::<6>(c"abcdé"), b"abcd");
+    assert_eq!(truncated::<7>(c"abcdé"), "abcdé".as_bytes());
+    assert_eq!(truncated::<2>(c"日本"), b"");
+}
+
+#[test]
+#[cfg(target_os = "aether")]
+fn get_name_after_set_name() {
+    crate::thread::spawn(|| {
+        // 14 bytes followed by a 3 byte character, which doesn't fit.
+        super::Thread::set_name(c"thread-name-ab€");
+        let name = super::Thread::get_name().unwrap();
+        assert_eq!(name.to_str(), Ok("thread-name-ab"));
+    })
+    .join()
+    .unwrap();
+}
@@ -152,7 +152,7 @@ unsafe fn fetch(name: &str) -> *mut libc::c_void {
     unsafe { libc::dlsym(libc::RTLD_DEFAULT, name.as_ptr()) }
 }
 
-#[cfg(not(any(target_os = "linux", target_os = "android")))]
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "aether")))]
 pub(crate) macro syscall {
     (fn $name:ident($($param:ident : $t:ty),* $(,)?) -> $ret:ty;) => (
         unsafe fn $name($($param: $t),*) -> $ret {
@@ -187,6 +187,30 @@ pub(crate) macro syscall {
     )
 }
 
+#[cfg(target_os = "aether")]
+pub(crate) macro syscall {
+    (
+        fn $name:ident($($param:ident : $t:ty),* $(,)?) -> $ret:ty;
+    ) => (
+        unsafe fn $name($($param: $t),*) -> $ret {
+            weak!(fn $name($($param: $t),*) -> $ret;);
+
+            // Prefer the libc wrapper like on Linux, but aether's libc does not
+            // provide all of them, so fall back to issuing the call directly.
+            if let Some(fun) = $name.get() {
+                unsafe { fun($($param),*) }
+            } else {
+                unsafe {
+                    super::aether::syscall_errno(
+                        super::aether::nr::${concat(SYS_, $name)},
+                        &[$($param as usize),*],
+                    ) as $ret
+                }
+            }
+        }
+    )
+}
+
 #[cfg(any(target_os = "linux", target_os = "android"))]
 pub(crate) macro raw_syscall {
     (fn $name:ident($($param:ident : $t:ty),* $(,)?) -> $ret:ty;) => (
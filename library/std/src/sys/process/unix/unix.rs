@@ -420,6 +420,7 @@ impl Command {
     }
 
     #[cfg(not(any(
+        target_os = "aether",
         target_os = "freebsd",
         target_os = "illumos",
         all(target_os = "linux", target_env = "gnu"),
@@ -439,6 +440,7 @@ impl Command {
     // Only support platforms for which posix_spawn() can return ENOENT
     // directly.
     #[cfg(any(
+        target_os = "aether",
         target_os = "freebsd",
         target_os = "illumos",
         all(target_os = "linux", target_env = "gnu"),
@@ -597,7 +599,11 @@ impl Command {
         /// Some platforms can set a new working directory for a spawned process in the
         /// `posix_spawn` path. This function looks up the function pointer for adding
         /// such an action to a `posix_spawn_file_actions_t` struct.
-        #[cfg(not(any(all(target_os = "linux", target_env = "musl"), target_os = "cygwin")))]
+        #[cfg(not(any(
+            all(target_os = "linux", target_env = "musl"),
+            target_os = "cygwin",
+            target_os = "aether",
+        )))]
         fn get_posix_spawn_addchdir() -> Option<PosixSpawnAddChdirFn> {
             use crate::sys::weak::weak;
 
@@ -633,9 +639,14 @@ impl Command {
         /// of the symbol at compile time or know about it upfront.
         ///
         /// Cygwin doesn't support weak symbol, so just link it.
-        #[cfg(any(all(target_os = "linux", target_env = "musl"), target_os = "cygwin"))]
+        #[cfg(any(
+            all(target_os = "linux", target_env = "musl"),
+            target_os = "cygwin",
+            target_os = "aether",
+        ))]
         fn get_posix_spawn_addchdir() -> Option<PosixSpawnAddChdirFn> {
-            // Our minimum required musl supports this function, so we can just use it.
+            // Our minimum required musl supports this function, as does aether's libc, which is
+            // derived from it and always linked statically, so we can just use it.
             Some(libc::posix_spawn_file_actions_addchdir_np)
         }
 
@@ -983,6 +994,19 @@ impl Process {
         self.pid as u32
     }
 
+    /// Opens a new kernel handle to the process.
+    #[cfg(target_os = "aether")]
+    pub fn handle(&self) -> io::Result<sys::pal::aether::Handle> {
+        // Once the child has been reaped its pid may name an unrelated process.
+        if self.status.is_some() {
+            return Err(io::const_error!(
+                ErrorKind::InvalidInput,
+                "the child process has already been reaped",
+            ));
+        }
+        sys::pal::aether::pidfd_open(self.pid)
+    }
+
     pub fn kill(&mut self) -> io::Result<()> {
         // If we've already waited on this process then the pid can be recycled
         // and used for another process, and we probably shouldn't be killing
@@ -995,6 +1019,9 @@ impl Process {
             // pidfd_send_signal predates pidfd_open. so if we were able to get an fd then sending signals will work too
             return pid_fd.kill();
         }
+        #[cfg(target_os = "aether")]
+        return sys::pal::aether::kill(self.pid, libc::SIGKILL);
+        #[cfg(not(target_os = "aether"))]
         cvt(unsafe { libc::kill(self.pid, libc::SIGKILL) }).map(drop)
     }
 
@@ -1292,6 +1319,24 @@ mod linux_child_ext {
     }
 }
 
+#[cfg(target_os = "aether")]
+mod aether_child_ext {
+    use crate::io;
+    use crate::os::aether::process::{ChildExt, RawPid};
+    use crate::os::fd::OwnedFd;
+
+    #[unstable(feature = "aether_ext", issue = "none")]
+    impl ChildExt for crate::process::Child {
+        fn raw_pid(&self) -> RawPid {
+            self.handle.pid
+        }
+
+        fn duplicate_handle(&self) -> io::Result<OwnedFd> {
+            self.handle.handle().map(OwnedFd::from)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests;
 
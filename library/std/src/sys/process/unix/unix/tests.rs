@@ -75,3 +75,25 @@ fn test_command_fork_no_unwind() {
             || signal == libc::SIGSEGV
     );
 }
+
+#[test]
+#[cfg(target_os = "aether")]
+fn test_posix_spawn_fast_path() {
+    use crate::ffi::OsStr;
+    use crate::sys::process::{Command, Stdio};
+
+    // `current_dir` and `process_group` are expressed as spawn actions.
+    let mut cmd = Command::new(OsStr::new("true"));
+    cmd.cwd(OsStr::new("/"));
+    cmd.pgroup(0);
+    let (_ours, theirs) = cmd.setup_io(Stdio::Null, false).unwrap();
+    let mut child = cmd
+        .posix_spawn(&theirs, None)
+        .unwrap()
+        .expect("expected the posix_spawn path to be taken");
+    assert!(child.wait().unwrap().success());
+
+    // A `pre_exec` closure can't be, so the fork/exec fallback is used.
+    unsafe { cmd.pre_exec(Box::new(|| Ok(()))) };
+    assert!(cmd.posix_spawn(&theirs, None).unwrap().is_none());
+}
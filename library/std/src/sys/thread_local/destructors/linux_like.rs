@@ -8,11 +8,15 @@
 //!
 //! Unfortunately, our minimum supported glibc version (at the time of writing)
 //! is 2.17, so we can only link this symbol weakly and need to use the
-//! [`list`](super::list) destructor implementation as fallback.
+//! [`list`](super::list) destructor implementation as fallback. The same goes
+//! for aether, whose libc only exports the symbol in newer builds.
 
-use crate::mem::transmute;
+use crate::ffi::c_void;
 
-pub unsafe fn register(t: *mut u8, dtor: unsafe extern "C" fn(*mut u8)) {
+/// `dtor` takes a `*mut c_void` rather than a `*mut u8` so that, with KCFI,
+/// its type id is the one of `void (*)(void *)` that the C library checks
+/// before calling it.
+pub unsafe fn register(t: *mut c_void, dtor: unsafe extern "C" fn(*mut c_void)) {
     /// This is necessary because the __cxa_thread_atexit_impl implementation
     /// std links to by default may be a C or C++ implementation that was not
     /// compiled using the Clang integer normalization option.
@@ -29,23 +33,13 @@ pub unsafe fn register(t: *mut u8, dtor: unsafe extern "C" fn(*mut u8)) {
         static __dso_handle: *mut u8;
         #[linkage = "extern_weak"]
         static __cxa_thread_atexit_impl: Option<
-            extern "C" fn(
-                unsafe extern "C" fn(*mut libc::c_void),
-                *mut libc::c_void,
-                *mut libc::c_void,
-            ) -> c_int,
+            extern "C" fn(unsafe extern "C" fn(*mut c_void), *mut c_void, *mut c_void) -> c_int,
         >;
     }
 
     if let Some(f) = unsafe { __cxa_thread_atexit_impl } {
         unsafe {
-            f(
-                transmute::<unsafe extern "C" fn(*mut u8), unsafe extern "C" fn(*mut libc::c_void)>(
-                    dtor,
-                ),
-                t.cast(),
-                (&raw const __dso_handle) as *mut _,
-            );
+            f(dtor, t, (&raw const __dso_handle) as *mut _);
         }
     } else {
         unsafe {
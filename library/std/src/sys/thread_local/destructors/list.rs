@@ -1,10 +1,12 @@
 use crate::cell::RefCell;
+use crate::ffi::c_void;
 use crate::sys::thread_local::guard;
 
 #[thread_local]
-static DTORS: RefCell<Vec<(*mut u8, unsafe extern "C" fn(*mut u8))>> = RefCell::new(Vec::new());
+static DTORS: RefCell<Vec<(*mut c_void, unsafe extern "C" fn(*mut c_void))>> =
+    RefCell::new(Vec::new());
 
-pub unsafe fn register(t: *mut u8, dtor: unsafe extern "C" fn(*mut u8)) {
+pub unsafe fn register(t: *mut c_void, dtor: unsafe extern "C" fn(*mut c_void)) {
     let Ok(mut dtors) = DTORS.try_borrow_mut() else {
         // This point can only be reached if the global allocator calls this
         // function again.
@@ -7,9 +7,16 @@ use crate::sys::thread_local::key::{LazyKey, set};
 
 #[cfg(target_thread_local)]
 pub fn enable() {
+    use crate::ffi::c_void;
+    use crate::mem::transmute;
     use crate::sys::thread_local::destructors;
 
-    static DTORS: LazyKey = LazyKey::new(Some(run));
+    // The C library calls key destructors as `void (*)(void *)`. With KCFI
+    // enabled, that call checks the type id of `run`, so it is declared with
+    // exactly that signature and only cast to the type the key stores.
+    static DTORS: LazyKey = LazyKey::new(Some(unsafe {
+        transmute::<unsafe extern "C" fn(*mut c_void), unsafe extern "C" fn(*mut u8)>(run)
+    }));
 
     // Setting the key value to something other than NULL will result in the
     // destructor being run at thread exit.
@@ -17,7 +24,7 @@ pub fn enable() {
         set(DTORS.force(), ptr::without_provenance_mut(1));
     }
 
-    unsafe extern "C" fn run(_: *mut u8) {
+    unsafe extern "C" fn run(_: *mut c_void) {
         unsafe {
             destructors::run();
             // On platforms with `__cxa_thread_atexit_impl`, `destructors::run`
@@ -58,6 +58,7 @@ cfg_if::cfg_if! {
 pub(crate) mod destructors {
     cfg_if::cfg_if! {
         if #[cfg(any(
+            target_os = "aether",
             target_os = "linux",
             target_os = "android",
             target_os = "fuchsia",
@@ -1,4 +1,5 @@
 use crate::cell::{Cell, UnsafeCell};
+use crate::ffi::c_void;
 use crate::ptr::{self, drop_in_place};
 use crate::sys::thread_local::{abort_on_dtor_unwind, destructors};
 
@@ -62,7 +63,7 @@ impl<T> Storage<T> {
 /// * Must only be called at thread destruction.
 /// * `ptr` must point to an instance of `Storage` with `Alive` state and be
 ///   valid for accessing that instance.
-unsafe extern "C" fn destroy<T>(ptr: *mut u8) {
+unsafe extern "C" fn destroy<T>(ptr: *mut c_void) {
     // Print a nice abort message if a panic occurs.
     abort_on_dtor_unwind(|| {
         let storage = unsafe { &*(ptr as *const Storage<T>) };
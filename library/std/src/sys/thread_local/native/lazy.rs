@@ -1,4 +1,5 @@
 use crate::cell::UnsafeCell;
+use crate::ffi::c_void;
 use crate::hint::unreachable_unchecked;
 use crate::ptr;
 use crate::sys::thread_local::{abort_on_dtor_unwind, destructors};
@@ -92,7 +93,7 @@ where
 /// * Must only be called at thread destruction.
 /// * `ptr` must point to an instance of `Storage<T, ()>` and be valid for
 ///   accessing that instance.
-unsafe extern "C" fn destroy<T>(ptr: *mut u8) {
+unsafe extern "C" fn destroy<T>(ptr: *mut c_void) {
     // Print a nice abort message if a panic occurs.
     abort_on_dtor_unwind(|| {
         let storage = unsafe { &*(ptr as *const Storage<T, ()>) };
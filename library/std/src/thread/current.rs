@@ -218,13 +218,29 @@ pub fn current() -> Thread {
     }
 }
 
+/// Returns the name the OS has for the current thread, so that threads created
+/// outside of Rust are reported under the name they were given there. The main
+/// thread is still called "main".
+#[cfg(target_os = "aether")]
+fn os_name(id: ThreadId) -> Option<String> {
+    if super::main_thread::get().is_none_or(|main| main == id) {
+        return None;
+    }
+    crate::sys::thread::Thread::get_name()?.into_string().ok()
+}
+
+#[cfg(not(target_os = "aether"))]
+fn os_name(_id: ThreadId) -> Option<String> {
+    None
+}
+
 #[cold]
 fn init_current(current: *mut ()) -> Thread {
     if current == NONE {
         CURRENT.set(BUSY);
         // If the thread ID was initialized already, use it.
         let id = id::get_or_init();
-        let thread = Thread::new(id, None);
+        let thread = Thread::new(id, os_name(id));
 
         // Make sure that `crate::rt::thread_cleanup` will be run, which will
         // call `drop_current`.
@@ -939,3 +939,21 @@ fn test_algebraic() {
     assert_approx_eq!(a.algebraic_div(b), a / b, eps);
     assert_approx_eq!(a.algebraic_rem(b), a % b, eps);
 }
+
+/// The userspace aether target does float math in SSE registers, so
+/// formatting, parsing and `cmath` must agree exactly with other targets.
+#[test]
+#[cfg(target_os = "aether")]
+fn test_aether_hard_float() {
+    assert!(cfg!(target_feature = "sse2"));
+
+    assert_eq!(format!("{}", 0.1f64 + 0.2), "0.30000000000000004");
+    assert_eq!(format!("{:e}", f64::MAX), "1.7976931348623157e308");
+    assert_eq!("2.5e-3".parse::<f64>(), Ok(0.0025));
+    assert_eq!("4.9e-324".parse::<f64>(), Ok(f64::from_bits(1)));
+
+    assert_eq!(2f64.sqrt(), consts::SQRT_2);
+    assert_approx_eq!(1f64.exp(), consts::E);
+    assert_approx_eq!(3f64.hypot(4.0), 5.0);
+    assert_approx_eq!(1f64.atan2(1.0), consts::FRAC_PI_4);
+}
@@ -0,0 +1,59 @@
+//! Runs code paths that cross between Rust and an instrumented C library,
+//! where a sanitized std can trip the sanitizer without any bug in the
+//! program itself. Only built with the `sanitizers` feature.
+
+use std::cell::Cell;
+use std::ffi::c_void;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+/// libc calls the start routine of a thread and the destructors of its
+/// thread locals through function pointers, which KCFI checks against the
+/// C types `void *(*)(void *)` and `void (*)(void *)`.
+#[test]
+fn thread_and_tls_destructor_callbacks() {
+    struct SetOnDrop(Arc<AtomicBool>);
+
+    impl Drop for SetOnDrop {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::Relaxed);
+        }
+    }
+
+    thread_local! {
+        static GUARD: Cell<Option<SetOnDrop>> = const { Cell::new(None) };
+    }
+
+    let dropped = Arc::new(AtomicBool::new(false));
+    let flag = dropped.clone();
+    thread::spawn(move || GUARD.set(Some(SetOnDrop(flag))))
+        .join()
+        .unwrap();
+    assert!(dropped.load(Ordering::Relaxed));
+}
+
+/// Blocks freed by Rust are poisoned for KASAN, so they must be unpoisoned
+/// again before libc can hand them out to someone else.
+#[test]
+fn libc_reuses_blocks_freed_by_rust() {
+    unsafe extern "C" {
+        fn malloc(size: usize) -> *mut c_void;
+        fn free(ptr: *mut c_void);
+    }
+
+    for size in [8, 64, 1024] {
+        // Free more blocks than std keeps quarantined.
+        for _ in 0..4096 {
+            drop(vec![0xa5u8; size]);
+        }
+        for _ in 0..4096 {
+            unsafe {
+                let p = malloc(size) as *mut u8;
+                assert!(!p.is_null());
+                p.write_bytes(0x5a, size);
+                free(p.cast());
+            }
+        }
+    }
+}
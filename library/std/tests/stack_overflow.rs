@@ -0,0 +1,69 @@
+//! Overflowing the stack of the main thread or of a spawned thread must kill
+//! the process, and is reported as a stack overflow wherever std knows the
+//! location of the guard page.
+//!
+//! The two stacks are guarded differently: the main thread's guard is found
+//! from the process stack limits, a spawned thread's from its pthread
+//! attributes, so both are checked.
+
+fn main() {
+    #[cfg(all(not(miri), any(target_os = "linux", target_os = "aether")))]
+    {
+        use std::hint::black_box;
+        use std::os::unix::process::ExitStatusExt;
+        use std::{env, process, thread};
+
+        const CHILD: &str = "STACK_OVERFLOW_TEST_CHILD";
+
+        match env::var(CHILD).as_deref() {
+            Ok("main") => overflow(0),
+            Ok("thread") => {
+                let child = thread::Builder::new()
+                    .name("overflower".to_owned())
+                    .spawn(|| overflow(0));
+                child.unwrap().join().unwrap()
+            }
+            _ => {
+                // musl's pthread_attr_getstack can't locate the kernel's guard
+                // below the main thread's stack, so on Linux with musl such an
+                // overflow is only caught by the kernel, as a plain SIGSEGV.
+                let main_reported = !cfg!(all(target_os = "linux", target_env = "musl"));
+                check("main", "main", main_reported);
+                check("thread", "overflower", true);
+            }
+        }
+
+        #[inline(never)]
+        #[allow(unconditional_recursion)]
+        fn overflow(depth: u64) -> ! {
+            let frame = black_box([depth; 64]);
+            overflow(black_box(frame[0] + 1))
+        }
+
+        fn check(which: &str, thread_name: &str, reported: bool) {
+            let me = env::current_exe().unwrap();
+            let output = process::Command::new(me)
+                .env(CHILD, which)
+                .output()
+                .unwrap();
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let report = stderr.contains(&format!("thread '{thread_name}'"))
+                && stderr.contains("has overflowed its stack");
+            if reported {
+                assert_eq!(
+                    output.status.signal(),
+                    Some(6),
+                    "{which}: not aborted, stderr: {stderr}"
+                );
+                assert!(report, "{which}: overflow not reported, stderr: {stderr}");
+            } else {
+                assert_eq!(
+                    output.status.signal(),
+                    Some(11),
+                    "{which}: no SIGSEGV, stderr: {stderr}"
+                );
+                assert!(!report, "{which}: unexpected report, stderr: {stderr}");
+            }
+        }
+    }
+}
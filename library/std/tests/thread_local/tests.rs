@@ -404,3 +404,64 @@ fn thread_current_in_dtor() {
     let name = name.as_ref().unwrap();
     assert_eq!(name, "test");
 }
+
+// Rust thread-local destructors are registered with libc's
+// `__cxa_thread_atexit_impl` where it exists, so they interleave with those of
+// C++ `thread_local`s, registered here directly, in reverse order of
+// registration. Ones registered while the thread is being torn down run after
+// the others.
+#[cfg(all(
+    target_thread_local,
+    any(all(target_os = "linux", target_env = "gnu"), target_os = "aether")
+))]
+#[test]
+fn dtor_order_with_registration_during_teardown() {
+    use std::ffi::{c_int, c_void};
+    use std::ptr;
+
+    unsafe extern "C" {
+        fn __cxa_thread_atexit_impl(
+            dtor: unsafe extern "C" fn(*mut c_void),
+            arg: *mut c_void,
+            dso_handle: *mut c_void,
+        ) -> c_int;
+    }
+
+    struct Record(u8);
+    impl Drop for Record {
+        fn drop(&mut self) {
+            ORDER.lock().unwrap().push(self.0);
+            if self.0 == 1 {
+                let _ = LATE.try_with(|_| {});
+            }
+        }
+    }
+
+    unsafe extern "C" fn foreign_dtor(arg: *mut c_void) {
+        ORDER.lock().unwrap().push(arg.addr() as u8);
+    }
+
+    static ORDER: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+
+    thread_local! {
+        static FIRST: Record = Record(1);
+        static SECOND: Record = Record(2);
+        static THIRD: Record = const { Record(3) };
+        static LATE: Record = Record(4);
+    }
+
+    thread::spawn(|| {
+        FIRST.with(|_| {});
+        // A null DSO handle stands for the main executable.
+        let arg = ptr::without_provenance_mut(10);
+        assert_eq!(
+            unsafe { __cxa_thread_atexit_impl(foreign_dtor, arg, ptr::null_mut()) },
+            0
+        );
+        SECOND.with(|_| {});
+        THIRD.with(|_| {});
+    })
+    .join()
+    .unwrap();
+    assert_eq!(*ORDER.lock().unwrap(), [3, 2, 10, 1, 4]);
+}
@@ -44,10 +44,23 @@ cfg_if! {
         // this run-time detection logic is never called.
         #[path = "os/other.rs"]
         mod os;
+    } else if #[cfg(all(target_os = "aether", target_arch = "x86_64"))] {
+        // aether only enables the vector state for some processes.
+        #[path = "os/x86.rs"]
+        mod x86;
+        #[path = "os/aether.rs"]
+        mod os;
     } else if #[cfg(any(target_arch = "x86", target_arch = "x86_64"))] {
         // On x86/x86_64 no OS specific functionality is required.
         #[path = "os/x86.rs"]
         mod os;
+        // aether's policy only depends on CPUID and `XCR0`, so it is tested
+        // on every x86_64 host.
+        #[cfg(all(test, target_arch = "x86_64"))]
+        use os as x86;
+        #[cfg(all(test, target_arch = "x86_64"))]
+        #[path = "os/aether.rs"]
+        mod aether;
     } else if #[cfg(all(any(target_os = "linux", target_os = "android"), feature = "libc"))] {
         #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
         #[path = "os/riscv.rs"]
@@ -0,0 +1,174 @@
+//! Run-time feature detection for aether on x86_64.
+//!
+//! CPUID only reports what the processor implements. aether builds its kernel
+//! without SSE and only saves the vector registers of a process across context
+//! switches when it has enabled the matching state components, so features
+//! that touch that state are reported only if `XCR0` says they are enabled.
+
+use core::arch::x86_64::{__cpuid, _xgetbv};
+
+use crate::detect::{Feature, bit, cache};
+
+/// Features that execute on the `XMM` registers or wider.
+///
+/// The AVX and AVX-512 features are already checked against `XCR0` by the
+/// generic x86 code, but only on top of the SSE state, so they are listed here
+/// as well.
+const SSE_STATE_FEATURES: &[Feature] = &[
+    Feature::sse,
+    Feature::sse2,
+    Feature::sse3,
+    Feature::ssse3,
+    Feature::sse4_1,
+    Feature::sse4_2,
+    Feature::sse4a,
+    Feature::pclmulqdq,
+    Feature::aes,
+    Feature::sha,
+    Feature::f16c,
+    Feature::gfni,
+    Feature::vaes,
+    Feature::vpclmulqdq,
+    Feature::kl,
+    Feature::widekl,
+    Feature::xop,
+    Feature::fma,
+    Feature::avx,
+    Feature::avx2,
+    Feature::avxvnni,
+    Feature::avxifma,
+    Feature::avxvnniint8,
+    Feature::avxneconvert,
+    Feature::avxvnniint16,
+    Feature::sha512,
+    Feature::sm3,
+    Feature::sm4,
+    Feature::avx512f,
+    Feature::avx512dq,
+    Feature::avx512ifma,
+    Feature::avx512pf,
+    Feature::avx512er,
+    Feature::avx512cd,
+    Feature::avx512bw,
+    Feature::avx512vl,
+    Feature::avx512vbmi,
+    Feature::avx512vbmi2,
+    Feature::avx512vnni,
+    Feature::avx512bitalg,
+    Feature::avx512vpopcntdq,
+    Feature::avx512vp2intersect,
+    Feature::avx512fp16,
+    Feature::avx512bf16,
+];
+
+pub(crate) fn detect_features() -> cache::Initializer {
+    restrict_to_os_state(super::x86::detect_features(), xcr0())
+}
+
+/// Reads `XCR0`, or returns `None` if the OS has not enabled `XGETBV`.
+fn xcr0() -> Option<u64> {
+    // This is safe because every x86_64 CPU has `cpuid`.
+    let ecx = unsafe { __cpuid(0x0000_0001_u32) }.ecx;
+    // `XSAVE` and `OSXSAVE`. Without the latter `XGETBV` raises #UD.
+    if !bit::test(ecx as usize, 26) || !bit::test(ecx as usize, 27) {
+        return None;
+    }
+    // This is safe because the CPU supports `xsave` and the OS has set
+    // `osxsave`.
+    Some(unsafe { _xgetbv(0) })
+}
+
+/// Drops the features whose register state the OS does not preserve.
+///
+/// Without `OSXSAVE` there is no way to tell from userspace whether aether
+/// saves the vector state, so those features are assumed to be unusable. The
+/// exception is a binary built with SSE2, which could not be running if its
+/// `XMM` registers were not preserved, so it keeps `sse` and `sse2`.
+/// Under-reporting is harmless; over-reporting crashes the caller.
+fn restrict_to_os_state(mut value: cache::Initializer, xcr0: Option<u64>) -> cache::Initializer {
+    let keep_baseline = xcr0.is_none() && cfg!(target_feature = "sse2");
+    // Test `XCR0.SSE[1]`.
+    let os_sse_support = xcr0.is_some_and(|xcr0| xcr0 & 0b10 != 0);
+    if !os_sse_support {
+        for &feature in SSE_STATE_FEATURES {
+            if !(keep_baseline && matches!(feature, Feature::sse | Feature::sse2)) {
+                value.unset(feature as u32);
+            }
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Features that the OS state doesn't affect.
+    const OTHER_FEATURES: &[Feature] = &[
+        Feature::popcnt,
+        Feature::bmi2,
+        Feature::xsave,
+        Feature::xsaveopt,
+        Feature::xsavec,
+        Feature::xsaves,
+    ];
+
+    fn all_features() -> cache::Initializer {
+        let mut value = cache::Initializer::default();
+        for &feature in SSE_STATE_FEATURES.iter().chain(OTHER_FEATURES) {
+            value.set(feature as u32);
+        }
+        value
+    }
+
+    fn assert_others_kept(value: cache::Initializer) {
+        for &feature in OTHER_FEATURES {
+            assert!(value.test(feature as u32), "{:?}", feature.to_str());
+        }
+    }
+
+    #[test]
+    fn sse_state_enabled() {
+        // x87, SSE and AVX.
+        let value = restrict_to_os_state(all_features(), Some(0b111));
+        assert!(value.test(Feature::sse2 as u32));
+        assert!(value.test(Feature::avx2 as u32));
+        assert_others_kept(value);
+    }
+
+    #[test]
+    fn sse_state_disabled() {
+        // x87 only.
+        let value = restrict_to_os_state(all_features(), Some(0b1));
+        for &feature in SSE_STATE_FEATURES {
+            assert!(!value.test(feature as u32), "{:?}", feature.to_str());
+        }
+        assert_others_kept(value);
+    }
+
+    #[test]
+    fn sse_state_unknown() {
+        let value = restrict_to_os_state(all_features(), None);
+        for &feature in SSE_STATE_FEATURES {
+            let baseline = matches!(feature, Feature::sse | Feature::sse2);
+            let expected = baseline && cfg!(target_feature = "sse2");
+            assert_eq!(
+                value.test(feature as u32),
+                expected,
+                "{:?}",
+                feature.to_str()
+            );
+        }
+        assert_others_kept(value);
+    }
+
+    #[test]
+    fn detected_features_are_enabled_by_the_os() {
+        let value = detect_features();
+        match xcr0() {
+            Some(xcr0) if xcr0 & 0b10 == 0 => assert!(!value.test(Feature::sse2 as u32)),
+            Some(_) => {}
+            None => assert!(!value.test(Feature::sse3 as u32)),
+        }
+    }
+}
@@ -3,9 +3,11 @@
 use std::env;
 use std::io::{self, IsTerminal, Write};
 use std::path::PathBuf;
+use std::time::Duration;
 
-use super::options::{ColorConfig, Options, OutputFormat, RunIgnored};
+use super::options::{ColorConfig, Options, OutputFormat, Partition, RunIgnored};
 use super::time::TestTimeOptions;
+use super::types::TestDesc;
 
 #[derive(Debug)]
 pub struct TestOpts {
@@ -25,11 +27,22 @@ pub struct TestOpts {
     pub shuffle_seed: Option<u64>,
     pub test_threads: Option<usize>,
     pub skip: Vec<String>,
+    /// Run only this share of the tests that pass the filters.
+    pub partition: Option<Partition>,
     pub time_options: Option<TestTimeOptions>,
+    /// Fail tests that are still running after this long. Tests run in a
+    /// child process are killed; tests run in-process are abandoned.
+    pub test_timeout: Option<Duration>,
+    /// Run failing tests again up to this many times. Only static tests can
+    /// be retried, which leaves out doctests and the tests of custom
+    /// harnesses.
+    pub retries: usize,
     /// Stop at first failing test.
-    /// May run a few more tests due to threading, but will
-    /// abort as soon as possible.
+    /// Tests that are already running are allowed to finish; the tests
+    /// that were not started are reported as not run.
     pub fail_fast: bool,
+    /// Stop starting tests once this many have failed, like `fail_fast`.
+    pub max_failures: Option<usize>,
     pub options: Options,
 }
 
@@ -41,6 +54,16 @@ impl TestOpts {
             ColorConfig::NeverColor => false,
         }
     }
+
+    /// Returns the timeout of the test described by `desc`, if it has one.
+    pub(crate) fn test_timeout_for(&self, desc: &TestDesc) -> Option<Duration> {
+        desc.timeout.or(self.test_timeout)
+    }
+
+    /// Returns how often the test described by `desc` may be run again.
+    pub(crate) fn retries_for(&self, desc: &TestDesc) -> usize {
+        desc.retries.unwrap_or(self.retries)
+    }
 }
 
 /// Result of parsing the options.
@@ -92,6 +115,16 @@ fn optgroups() -> getopts::Options {
              be used multiple times)",
             "FILTER",
         )
+        .optopt(
+            "",
+            "partition",
+            "Run only the INDEXth of COUNT shares of the tests, counting
+            from 1, to split a test suite across several runs:
+            hash  = pick tests by a hash of their name, which keeps a
+                    test in the same share as tests are added;
+            count = split the tests, sorted by name, into equal slices",
+            "hash|count:INDEX/COUNT",
+        )
         .optflag(
             "q",
             "quiet",
@@ -119,8 +152,9 @@ fn optgroups() -> getopts::Options {
             pretty = Print verbose output;
             terse  = Display one character per test;
             json   = Output a json document;
-            junit  = Output a JUnit document",
-            "pretty|terse|json|junit",
+            junit  = Output a JUnit document;
+            tap    = Output a TAP version 14 stream",
+            "pretty|terse|json|junit|tap",
         )
         .optflag(
             "",
@@ -163,6 +197,38 @@ fn optgroups() -> getopts::Options {
             `CRITICAL_TIME` here means the limit that should not be exceeded by test.
             ",
         )
+        .optopt(
+            "",
+            "test-timeout",
+            "Fail tests that are still running after SECS seconds.
+
+            With panic=abort the test's process is killed. Tests run
+            in-process can't be stopped, so they are left running in the
+            background and the run fails.",
+            "SECS",
+        )
+        .optopt(
+            "",
+            "retries",
+            "Run failing tests again, up to N times. Tests that pass on a
+            retry are reported as flaky and don't fail the run. Only tests
+            compiled into the test binary can be retried, not doctests or
+            the tests of custom harnesses.",
+            "N",
+        )
+        .optflag(
+            "",
+            "fail-fast",
+            "Stop starting tests after the first failure. Tests that are
+            already running are allowed to finish; the rest are reported
+            as not run.",
+        )
+        .optopt(
+            "",
+            "max-failures",
+            "Like --fail-fast, but stop after N tests have failed",
+            "N",
+        )
         .optflag("", "shuffle", "Run tests in random order")
         .optopt(
             "",
@@ -283,6 +349,11 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
     let force_run_in_process = unstable_optflag!(matches, allow_unstable, "force-run-in-process");
     let exclude_should_panic = unstable_optflag!(matches, allow_unstable, "exclude-should-panic");
     let time_options = get_time_options(&matches, allow_unstable)?;
+    let test_timeout = get_test_timeout(&matches, allow_unstable)?;
+    let partition = get_partition(&matches, allow_unstable)?;
+    let retries = get_retries(&matches, allow_unstable)?;
+    let fail_fast = unstable_optflag!(matches, allow_unstable, "fail-fast");
+    let max_failures = get_max_failures(&matches, allow_unstable)?;
     let shuffle = get_shuffle(&matches, allow_unstable)?;
     let shuffle_seed = get_shuffle_seed(&matches, allow_unstable)?;
 
@@ -326,9 +397,13 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
         shuffle_seed,
         test_threads,
         skip,
+        partition,
         time_options,
+        test_timeout,
+        retries,
         options,
-        fail_fast: false,
+        fail_fast,
+        max_failures,
     };
 
     Ok(test_opts)
@@ -365,6 +440,91 @@ fn get_time_options(
     Ok(options)
 }
 
+fn get_test_timeout(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<Option<Duration>> {
+    let test_timeout = match unstable_optopt!(matches, allow_unstable, "test-timeout") {
+        Some(secs) => match secs.parse::<u64>() {
+            Ok(0) => return Err("argument for --test-timeout must not be 0".to_string()),
+            Ok(secs) => Some(Duration::from_secs(secs)),
+            Err(e) => {
+                return Err(format!(
+                    "argument for --test-timeout must be a number > 0 \
+                     (error: {e})"
+                ));
+            }
+        },
+        None => None,
+    };
+
+    Ok(test_timeout)
+}
+
+fn get_retries(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<usize> {
+    let retries = match unstable_optopt!(matches, allow_unstable, "retries") {
+        Some(n) => match n.parse::<usize>() {
+            Ok(n) => n,
+            Err(e) => {
+                return Err(format!(
+                    "argument for --retries must be a number \
+                     (error: {e})"
+                ));
+            }
+        },
+        None => 0,
+    };
+
+    Ok(retries)
+}
+
+fn get_max_failures(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<Option<usize>> {
+    let max_failures = match unstable_optopt!(matches, allow_unstable, "max-failures") {
+        Some(n) => match n.parse::<usize>() {
+            Ok(0) => return Err("argument for --max-failures must not be 0".to_string()),
+            Ok(n) => Some(n),
+            Err(e) => {
+                return Err(format!(
+                    "argument for --max-failures must be a number > 0 \
+                     (error: {e})"
+                ));
+            }
+        },
+        None => None,
+    };
+
+    Ok(max_failures)
+}
+
+fn get_partition(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<Option<Partition>> {
+    let Some(arg) = unstable_optopt!(matches, allow_unstable, "partition") else {
+        return Ok(None);
+    };
+
+    let invalid = || {
+        format!(
+            "argument for --partition must be of the form hash:INDEX/COUNT or \
+             count:INDEX/COUNT, with 1 <= INDEX <= COUNT (was {arg})"
+        )
+    };
+    let (mode, share) = arg.split_once(':').ok_or_else(invalid)?;
+    let (index, total) = share.split_once('/').ok_or_else(invalid)?;
+    let (index, total) = match (index.parse::<usize>(), total.parse::<usize>()) {
+        (Ok(index @ 1..), Ok(total)) if index <= total => (index - 1, total),
+        _ => return Err(invalid()),
+    };
+    let partition = match mode {
+        "hash" => Partition::Hash { index, total },
+        "count" => Partition::Count { index, total },
+        _ => return Err(invalid()),
+    };
+
+    Ok(Some(partition))
+}
+
 fn get_shuffle(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<bool> {
     let mut shuffle = unstable_optflag!(matches, allow_unstable, "shuffle");
     if !shuffle && allow_unstable {
@@ -443,9 +603,15 @@ fn get_format(
             }
             OutputFormat::Junit
         }
+        Some("tap") => {
+            if !allow_unstable {
+                return Err("The \"tap\" format is only accepted on the nightly compiler with -Z unstable-options".into());
+            }
+            OutputFormat::Tap
+        }
         Some(v) => {
             return Err(format!(
-                "argument for --format must be pretty, terse, json or junit (was \
+                "argument for --format must be pretty, terse, json, junit or tap (was \
                  {v})"
             ));
         }
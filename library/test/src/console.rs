@@ -9,7 +9,7 @@ use super::bench::fmt_bench_samples;
 use super::cli::TestOpts;
 use super::event::{CompletedTest, TestEvent};
 use super::formatters::{
-    JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter, TerseFormatter,
+    JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter, TapFormatter, TerseFormatter,
 };
 use super::helpers::concurrency::get_concurrency;
 use super::helpers::metrics::MetricMap;
@@ -84,6 +84,12 @@ pub(crate) struct ConsoleTestState {
     pub total: usize,
     pub passed: usize,
     pub failed: usize,
+    /// Tests that passed only after being retried; not counted in `passed`.
+    pub flaky: usize,
+    /// Tests that were not started because the run was aborted.
+    pub not_run: usize,
+    /// Whether the run was stopped by `--fail-fast` or `--max-failures`.
+    pub aborted: bool,
     pub ignored: usize,
     pub filtered_out: usize,
     pub measured: usize,
@@ -108,6 +114,9 @@ impl ConsoleTestState {
             total: 0,
             passed: 0,
             failed: 0,
+            flaky: 0,
+            not_run: 0,
+            aborted: false,
             ignored: 0,
             filtered_out: 0,
             measured: 0,
@@ -163,6 +172,9 @@ impl ConsoleTestState {
                     }
                     TestResult::TrBench(ref bs) => fmt_bench_samples(bs),
                     TestResult::TrTimedFail => "failed (time limit exceeded)".to_owned(),
+                    TestResult::TrTimedOut => "failed (timed out)".to_owned(),
+                    TestResult::TrFlaky(runs) => format!("ok (flaky, {runs} runs)"),
+                    TestResult::TrNotRun => "not run".to_owned(),
                 },
                 name,
             )
@@ -174,7 +186,7 @@ impl ConsoleTestState {
     }
 
     fn current_test_count(&self) -> usize {
-        self.passed + self.failed + self.ignored + self.measured
+        self.passed + self.failed + self.flaky + self.not_run + self.ignored + self.measured
     }
 }
 
@@ -186,7 +198,7 @@ pub(crate) fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) ->
     };
 
     let mut out: Box<dyn OutputFormatter> = match opts.format {
-        OutputFormat::Pretty | OutputFormat::Junit => {
+        OutputFormat::Pretty | OutputFormat::Junit | OutputFormat::Tap => {
             Box::new(PrettyFormatter::new(output, false, 0, false, None))
         }
         OutputFormat::Terse => Box::new(TerseFormatter::new(output, false, 0, false)),
@@ -229,6 +241,13 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
             st.passed += 1;
             st.not_failures.push((test, stdout));
         }
+        TestResult::TrFlaky(_) => {
+            st.flaky += 1;
+            st.not_failures.push((test, stdout));
+        }
+        TestResult::TrNotRun => {
+            st.not_run += 1;
+        }
         TestResult::TrIgnored => {
             st.ignored += 1;
             st.ignores.push((test, stdout));
@@ -255,6 +274,12 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
             st.failed += 1;
             st.time_failures.push((test, stdout));
         }
+        TestResult::TrTimedOut => {
+            st.failed += 1;
+            let mut stdout = stdout;
+            stdout.extend_from_slice(b"note: test did not finish before the timeout");
+            st.failures.push((test, stdout));
+        }
     }
 }
 
@@ -275,6 +300,7 @@ fn on_test_event(
         }
         TestEvent::TeWait(ref test) => out.write_test_start(test)?,
         TestEvent::TeTimeout(ref test) => out.write_timeout(test)?,
+        TestEvent::TeAborted => st.aborted = true,
         TestEvent::TeResult(completed_test) => {
             let test = &completed_test.desc;
             let result = &completed_test.result;
@@ -322,6 +348,7 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
         )),
         OutputFormat::Json => Box::new(JsonFormatter::new(output)),
         OutputFormat::Junit => Box::new(JunitFormatter::new(output)),
+        OutputFormat::Tap => Box::new(TapFormatter::new(output)),
     };
     let mut st = ConsoleTestState::new(opts)?;
 
@@ -335,7 +362,7 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
     run_tests(opts, tests, |x| on_test_event(&x, &mut st, &mut *out))?;
     st.exec_time = start_time.map(|t| TestSuiteExecTime(t.elapsed()));
 
-    assert!(opts.fail_fast || st.current_test_count() == st.total);
+    assert_eq!(st.current_test_count(), st.total);
 
     out.write_run_finish(&st)
 }
@@ -39,4 +39,7 @@ pub enum TestEvent {
     TeResult(CompletedTest),
     TeTimeout(TestDesc),
     TeFilteredOut(usize),
+    /// The run was stopped by `--fail-fast` or `--max-failures`. Sent once,
+    /// before the tests that were not run are reported.
+    TeAborted,
 }
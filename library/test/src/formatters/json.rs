@@ -144,6 +144,19 @@ impl<T: Write> OutputFormatter for JsonFormatter<T> {
                 self.write_event("test", desc.name.as_slice(), "ok", exec_time, stdout, None)
             }
 
+            TestResult::TrNotRun => {
+                self.write_event("test", desc.name.as_slice(), "not_run", None, None, None)
+            }
+
+            TestResult::TrFlaky(runs) => self.write_event(
+                "test",
+                desc.name.as_slice(),
+                "flaky",
+                exec_time,
+                stdout,
+                Some(&format!(r#""runs": {runs}"#)),
+            ),
+
             TestResult::TrFailed => self.write_event(
                 "test",
                 desc.name.as_slice(),
@@ -162,6 +175,15 @@ impl<T: Write> OutputFormatter for JsonFormatter<T> {
                 Some(r#""reason": "time limit exceeded""#),
             ),
 
+            TestResult::TrTimedOut => self.write_event(
+                "test",
+                desc.name.as_slice(),
+                "failed",
+                exec_time,
+                stdout,
+                Some(r#""reason": "timed out""#),
+            ),
+
             TestResult::TrFailedMsg(ref m) => self.write_event(
                 "test",
                 desc.name.as_slice(),
@@ -215,6 +237,9 @@ impl<T: Write> OutputFormatter for JsonFormatter<T> {
         let event = if state.failed == 0 { "ok" } else { "failed" };
         let passed = state.passed;
         let failed = state.failed;
+        let flaky = state.flaky;
+        let not_run = state.not_run;
+        let aborted = state.aborted;
         let ignored = state.ignored;
         let measured = state.measured;
         let filtered_out = state.filtered_out;
@@ -226,7 +251,7 @@ impl<T: Write> OutputFormatter for JsonFormatter<T> {
         let newline = "\n";
 
         self.writeln_message(&format!(
-            r#"{{ "type": "suite", "event": "{event}", "passed": {passed}, "failed": {failed}, "ignored": {ignored}, "measured": {measured}, "filtered_out": {filtered_out}{exec_time_json} }}{newline}"#
+            r#"{{ "type": "suite", "event": "{event}", "passed": {passed}, "failed": {failed}, "flaky": {flaky}, "not_run": {not_run}, "aborted": {aborted}, "ignored": {ignored}, "measured": {measured}, "filtered_out": {filtered_out}{exec_time_json} }}{newline}"#
         ))?;
 
         Ok(state.failed == 0)
@@ -235,7 +260,7 @@ impl<T: Write> OutputFormatter for JsonFormatter<T> {
 
 /// A formatting utility used to print strings with characters in need of escaping.
 /// Base code taken form `libserialize::json::escape_str`
-struct EscapedString<S: AsRef<str>>(S);
+pub(super) struct EscapedString<S: AsRef<str>>(pub(super) S);
 
 impl<S: AsRef<str>> std::fmt::Display for EscapedString<S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> ::std::fmt::Result {
@@ -21,6 +21,11 @@ impl<T: Write> JunitFormatter<T> {
         }
     }
 
+    #[cfg(test)]
+    pub(crate) fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
+    }
+
     fn write_message(&mut self, s: &str) -> io::Result<()> {
         assert!(!s.contains('\n'));
 
@@ -107,12 +112,25 @@ impl<T: Write> OutputFormatter for JunitFormatter<T> {
              tests=\"{}\" \
              skipped=\"{}\" \
              >",
-            state.failed, state.total, state.ignored
+            state.failed,
+            state.total,
+            state.ignored + state.not_run
         ))?;
         for (desc, result, duration, stdout) in std::mem::take(&mut self.results) {
             let (class_name, test_name) = parse_class_name(&desc);
             match result {
                 TestResult::TrIgnored => { /* no-op */ }
+                TestResult::TrNotRun => {
+                    self.write_message(&format!(
+                        "<testcase classname=\"{}\" \
+                         name=\"{}\" time=\"0\">",
+                        class_name, test_name
+                    ))?;
+                    self.write_message(
+                        "<skipped message=\"not run, the run was aborted early\"/>",
+                    )?;
+                    self.write_message("</testcase>")?;
+                }
                 TestResult::TrFailed => {
                     self.write_message(&format!(
                         "<testcase classname=\"{}\" \
@@ -159,6 +177,26 @@ impl<T: Write> OutputFormatter for JunitFormatter<T> {
                     self.write_message("</testcase>")?;
                 }
 
+                TestResult::TrTimedOut => {
+                    self.write_message(&format!(
+                        "<testcase classname=\"{}\" \
+                         name=\"{}\" time=\"{}\">",
+                        class_name,
+                        test_name,
+                        duration.as_secs_f64()
+                    ))?;
+                    self.write_message(
+                        "<failure message=\"test did not finish before the timeout\" \
+                         type=\"timeout\"/>",
+                    )?;
+                    if !stdout.is_empty() {
+                        self.write_message("<system-out>")?;
+                        self.write_message(&str_to_cdata(&String::from_utf8_lossy(&stdout)))?;
+                        self.write_message("</system-out>")?;
+                    }
+                    self.write_message("</testcase>")?;
+                }
+
                 TestResult::TrBench(ref b) => {
                     self.write_message(&format!(
                         "<testcase classname=\"benchmark::{}\" \
@@ -167,6 +205,30 @@ impl<T: Write> OutputFormatter for JunitFormatter<T> {
                     ))?;
                 }
 
+                TestResult::TrFlaky(runs) => {
+                    // Flaky tests pass, but are marked so that they can be
+                    // told apart from tests that passed on their first run.
+                    self.write_message(&format!(
+                        "<testcase classname=\"{}\" \
+                         name=\"{}\" time=\"{}\">",
+                        class_name,
+                        test_name,
+                        duration.as_secs_f64()
+                    ))?;
+                    self.write_message(&format!(
+                        "<properties>\
+                         <property name=\"flaky\" value=\"true\"/>\
+                         <property name=\"runs\" value=\"{runs}\"/>\
+                         </properties>"
+                    ))?;
+                    if !stdout.is_empty() && state.options.display_output {
+                        self.write_message("<system-out>")?;
+                        self.write_message(&str_to_cdata(&String::from_utf8_lossy(&stdout)))?;
+                        self.write_message("</system-out>")?;
+                    }
+                    self.write_message("</testcase>")?;
+                }
+
                 TestResult::TrOk => {
                     self.write_message(&format!(
                         "<testcase classname=\"{}\" \
@@ -49,6 +49,10 @@ impl<T: Write> PrettyFormatter<T> {
         self.write_short_result("FAILED", term::color::RED)
     }
 
+    pub(crate) fn write_flaky(&mut self, runs: usize) -> io::Result<()> {
+        self.write_short_result(&format!("ok (flaky, {runs} runs)"), term::color::YELLOW)
+    }
+
     pub(crate) fn write_ignored(&mut self, message: Option<&'static str>) -> io::Result<()> {
         if let Some(message) = message {
             self.write_short_result(&format!("ignored, {message}"), term::color::YELLOW)
@@ -57,10 +61,18 @@ impl<T: Write> PrettyFormatter<T> {
         }
     }
 
+    pub(crate) fn write_not_run(&mut self) -> io::Result<()> {
+        self.write_short_result("not run", term::color::YELLOW)
+    }
+
     pub(crate) fn write_time_failed(&mut self) -> io::Result<()> {
         self.write_short_result("FAILED (time limit exceeded)", term::color::RED)
     }
 
+    pub(crate) fn write_timed_out(&mut self) -> io::Result<()> {
+        self.write_short_result("FAILED (timed out)", term::color::RED)
+    }
+
     pub(crate) fn write_bench(&mut self) -> io::Result<()> {
         self.write_pretty("bench", term::color::CYAN)
     }
@@ -242,12 +254,15 @@ impl<T: Write> OutputFormatter for PrettyFormatter<T> {
         _: &[u8],
         _: &ConsoleTestState,
     ) -> io::Result<()> {
-        if self.is_multithreaded {
+        // Tests that were not run were never announced as started.
+        if self.is_multithreaded || *result == TestResult::TrNotRun {
             self.write_test_name(desc)?;
         }
 
         match *result {
             TestResult::TrOk => self.write_ok()?,
+            TestResult::TrNotRun => self.write_not_run()?,
+            TestResult::TrFlaky(runs) => self.write_flaky(runs)?,
             TestResult::TrFailed | TestResult::TrFailedMsg(_) => self.write_failed()?,
             TestResult::TrIgnored => self.write_ignored(desc.ignore_message)?,
             TestResult::TrBench(ref bs) => {
@@ -255,6 +270,7 @@ impl<T: Write> OutputFormatter for PrettyFormatter<T> {
                 self.write_plain(format!(": {}", fmt_bench_samples(bs)))?;
             }
             TestResult::TrTimedFail => self.write_time_failed()?,
+            TestResult::TrTimedOut => self.write_timed_out()?,
         }
 
         self.write_time(desc, exec_time)?;
@@ -292,9 +308,22 @@ impl<T: Write> OutputFormatter for PrettyFormatter<T> {
         } else {
             self.write_pretty("FAILED", term::color::RED)?;
         }
+        if state.aborted {
+            self.write_plain(" (aborted early)")?;
+        }
 
+        let flaky = if state.flaky > 0 {
+            format!("{} flaky; ", state.flaky)
+        } else {
+            String::new()
+        };
+        let not_run = if state.not_run > 0 {
+            format!("{} not run; ", state.not_run)
+        } else {
+            String::new()
+        };
         let s = format!(
-            ". {} passed; {} failed; {} ignored; {} measured; {} filtered out",
+            ". {} passed; {} failed; {flaky}{not_run}{} ignored; {} measured; {} filtered out",
             state.passed, state.failed, state.ignored, state.measured, state.filtered_out
         );
 
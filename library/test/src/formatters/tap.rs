@@ -0,0 +1,242 @@
+use std::io;
+use std::io::prelude::Write;
+
+use super::OutputFormatter;
+use super::json::EscapedString;
+use crate::bench::fmt_bench_samples;
+use crate::console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation};
+use crate::test_result::TestResult;
+use crate::time;
+use crate::types::TestDesc;
+
+/// Writes the results in the [Test Anything Protocol](https://testanything.org/), version 14.
+///
+/// Every result is a test point numbered in the order it is reported, which
+/// may differ from the order the tests were started in. Failures are followed
+/// by a YAML block with the failure message and the captured output.
+pub(crate) struct TapFormatter<T> {
+    out: OutputLocation<T>,
+    /// Number of test points written so far.
+    test_point: usize,
+}
+
+impl<T: Write> TapFormatter<T> {
+    pub(crate) fn new(out: OutputLocation<T>) -> Self {
+        Self { out, test_point: 0 }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
+    }
+
+    fn writeln_message(&mut self, s: &str) -> io::Result<()> {
+        // Write whole lines at once so that the lines of concurrently
+        // reported tests can't interleave.
+        assert_eq!(s.chars().last(), Some('\n'));
+
+        self.out.write_all(s.as_ref())
+    }
+
+    fn write_test_point(
+        &mut self,
+        ok: bool,
+        desc: &TestDesc,
+        directive: Option<&str>,
+        diagnostics: Option<String>,
+    ) -> io::Result<()> {
+        self.test_point += 1;
+        let status = if ok { "ok" } else { "not ok" };
+        let mut line = format!("{status} {} - {}", self.test_point, TapDescription(desc));
+        if let Some(directive) = directive {
+            line.push_str(" # ");
+            line.push_str(directive);
+        }
+        line.push('\n');
+        if let Some(diagnostics) = diagnostics {
+            line.push_str("  ---\n");
+            line.push_str(&diagnostics);
+            line.push_str("  ...\n");
+        }
+        self.writeln_message(&line)
+    }
+}
+
+/// Builds the body of a YAML diagnostic block, indented to sit under a test
+/// point.
+fn yaml_diagnostics(
+    message: Option<&str>,
+    exec_time: Option<&time::TestExecTime>,
+    stdout: &[u8],
+) -> String {
+    let mut yaml = String::new();
+    if let Some(message) = message {
+        // JSON strings are valid double-quoted YAML scalars.
+        yaml.push_str(&format!("  message: \"{}\"\n", EscapedString(message)));
+    }
+    if let Some(exec_time) = exec_time {
+        yaml.push_str(&format!(
+            "  duration_ms: {}\n",
+            exec_time.0.as_secs_f64() * 1000.0
+        ));
+    }
+    if !stdout.is_empty() {
+        // The indentation indicator keeps output that starts with spaces
+        // from being taken as part of the indentation.
+        yaml.push_str("  stdout: |2\n");
+        for line in String::from_utf8_lossy(stdout).lines() {
+            yaml.push_str("    ");
+            yaml.push_str(line);
+            yaml.push('\n');
+        }
+    }
+    yaml
+}
+
+/// The description of a test point. A `#` would start a directive, so it is
+/// escaped, as are the backslashes that could escape it.
+struct TapDescription<'a>(&'a TestDesc);
+
+impl std::fmt::Display for TapDescription<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for c in self.0.name.as_slice().chars() {
+            match c {
+                '#' => f.write_str("\\#")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str(" ")?,
+                c => write!(f, "{c}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: Write> OutputFormatter for TapFormatter<T> {
+    fn write_discovery_start(&mut self) -> io::Result<()> {
+        Err(io::const_error!(
+            io::ErrorKind::NotFound,
+            "not yet implemented!"
+        ))
+    }
+
+    fn write_test_discovered(&mut self, _desc: &TestDesc, _test_type: &str) -> io::Result<()> {
+        Err(io::const_error!(
+            io::ErrorKind::NotFound,
+            "not yet implemented!"
+        ))
+    }
+
+    fn write_discovery_finish(&mut self, _state: &ConsoleTestDiscoveryState) -> io::Result<()> {
+        Err(io::const_error!(
+            io::ErrorKind::NotFound,
+            "not yet implemented!"
+        ))
+    }
+
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
+        self.writeln_message("TAP version 14\n")?;
+        self.writeln_message(&format!("1..{test_count}\n"))?;
+        if let Some(shuffle_seed) = shuffle_seed {
+            self.writeln_message(&format!("# shuffle seed: {shuffle_seed}\n"))?;
+        }
+        Ok(())
+    }
+
+    fn write_test_start(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        // We do not output anything on test start.
+        Ok(())
+    }
+
+    fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
+        self.writeln_message(&format!(
+            "# {} has been running for over {} seconds\n",
+            TapDescription(desc),
+            time::TEST_WARN_TIMEOUT_S
+        ))
+    }
+
+    fn write_result(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        exec_time: Option<&time::TestExecTime>,
+        stdout: &[u8],
+        state: &ConsoleTestState,
+    ) -> io::Result<()> {
+        match *result {
+            TestResult::TrOk => {
+                let diagnostics = (state.options.display_output && !stdout.is_empty())
+                    .then(|| yaml_diagnostics(None, exec_time, stdout));
+                self.write_test_point(true, desc, None, diagnostics)
+            }
+
+            TestResult::TrFlaky(runs) => {
+                let message = format!("flaky: passed after {runs} runs");
+                let diagnostics = yaml_diagnostics(Some(&message), exec_time, stdout);
+                self.write_test_point(true, desc, None, Some(diagnostics))
+            }
+
+            TestResult::TrFailed => {
+                let diagnostics = yaml_diagnostics(Some("test failed"), exec_time, stdout);
+                self.write_test_point(false, desc, None, Some(diagnostics))
+            }
+
+            TestResult::TrFailedMsg(ref m) => {
+                let diagnostics = yaml_diagnostics(Some(m), exec_time, stdout);
+                self.write_test_point(false, desc, None, Some(diagnostics))
+            }
+
+            TestResult::TrTimedFail => {
+                let diagnostics = yaml_diagnostics(Some("time limit exceeded"), exec_time, stdout);
+                self.write_test_point(false, desc, None, Some(diagnostics))
+            }
+
+            TestResult::TrTimedOut => {
+                let diagnostics = yaml_diagnostics(Some("timed out"), exec_time, stdout);
+                self.write_test_point(false, desc, None, Some(diagnostics))
+            }
+
+            TestResult::TrNotRun => self.write_test_point(
+                true,
+                desc,
+                Some("SKIP not run, the run was aborted early"),
+                None,
+            ),
+
+            TestResult::TrIgnored => {
+                let directive = match desc.ignore_message {
+                    Some(msg) => format!("SKIP {msg}"),
+                    None => "SKIP".to_owned(),
+                };
+                self.write_test_point(true, desc, Some(&directive), None)
+            }
+
+            TestResult::TrBench(ref bs) => {
+                let summary = fmt_bench_samples(bs);
+                self.write_test_point(true, desc, Some(&summary), None)
+            }
+        }
+    }
+
+    fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
+        let flaky = if state.flaky > 0 {
+            format!("flaky: {}; ", state.flaky)
+        } else {
+            String::new()
+        };
+        let not_run = if state.not_run > 0 {
+            format!("not run: {}; ", state.not_run)
+        } else {
+            String::new()
+        };
+        self.writeln_message(&format!(
+            "# passed: {}; failed: {}; {flaky}{not_run}ignored: {}; measured: {}; filtered out: {}\n",
+            state.passed, state.failed, state.ignored, state.measured, state.filtered_out
+        ))?;
+        if state.aborted {
+            self.writeln_message("# the run was aborted early\n")?;
+        }
+
+        Ok(state.failed == 0)
+    }
+}
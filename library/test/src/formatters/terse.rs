@@ -49,7 +49,20 @@ impl<T: Write> TerseFormatter<T> {
     pub(crate) fn write_failed(&mut self, name: &str) -> io::Result<()> {
         // Put failed tests on their own line and include the test name, so that it's faster
         // to see which test failed without having to wait for them all to run.
+        self.write_named_result(name, "FAILED", term::color::RED)
+    }
+
+    pub(crate) fn write_flaky(&mut self, name: &str) -> io::Result<()> {
+        // Flaky tests get their own line as well, so that they don't go unnoticed.
+        self.write_named_result(name, "flaky", term::color::YELLOW)
+    }
 
+    fn write_named_result(
+        &mut self,
+        name: &str,
+        result: &str,
+        color: term::color::Color,
+    ) -> io::Result<()> {
         // normally, we write the progress unconditionally, even if the previous line was cut short.
         // but if this is the very first column, no short results will have been printed and we'll end up with *only* the progress on the line.
         // avoid this.
@@ -58,7 +71,7 @@ impl<T: Write> TerseFormatter<T> {
         }
         self.test_count += 1;
         self.write_plain(format!("{name} --- "))?;
-        self.write_pretty("FAILED", term::color::RED)?;
+        self.write_pretty(result, color)?;
         self.write_plain("\n")
     }
 
@@ -66,6 +79,10 @@ impl<T: Write> TerseFormatter<T> {
         self.write_short_result("i", term::color::YELLOW)
     }
 
+    pub(crate) fn write_not_run(&mut self) -> io::Result<()> {
+        self.write_short_result("n", term::color::YELLOW)
+    }
+
     pub(crate) fn write_bench(&mut self) -> io::Result<()> {
         self.write_pretty("bench", term::color::CYAN)
     }
@@ -230,10 +247,13 @@ impl<T: Write> OutputFormatter for TerseFormatter<T> {
     ) -> io::Result<()> {
         match *result {
             TestResult::TrOk => self.write_ok(),
-            TestResult::TrFailed | TestResult::TrFailedMsg(_) | TestResult::TrTimedFail => {
-                self.write_failed(desc.name.as_slice())
-            }
+            TestResult::TrFlaky(_) => self.write_flaky(desc.name.as_slice()),
+            TestResult::TrFailed
+            | TestResult::TrFailedMsg(_)
+            | TestResult::TrTimedFail
+            | TestResult::TrTimedOut => self.write_failed(desc.name.as_slice()),
             TestResult::TrIgnored => self.write_ignored(),
+            TestResult::TrNotRun => self.write_not_run(),
             TestResult::TrBench(ref bs) => {
                 if self.is_multithreaded {
                     self.write_test_name(desc)?;
@@ -269,9 +289,22 @@ impl<T: Write> OutputFormatter for TerseFormatter<T> {
         } else {
             self.write_pretty("FAILED", term::color::RED)?;
         }
+        if state.aborted {
+            self.write_plain(" (aborted early)")?;
+        }
 
+        let flaky = if state.flaky > 0 {
+            format!("{} flaky; ", state.flaky)
+        } else {
+            String::new()
+        };
+        let not_run = if state.not_run > 0 {
+            format!("{} not run; ", state.not_run)
+        } else {
+            String::new()
+        };
         let s = format!(
-            ". {} passed; {} failed; {} ignored; {} measured; {} filtered out",
+            ". {} passed; {} failed; {flaky}{not_run}{} ignored; {} measured; {} filtered out",
             state.passed, state.failed, state.ignored, state.measured, state.filtered_out
         );
 
@@ -3,4 +3,5 @@
 
 pub(crate) mod concurrency;
 pub(crate) mod metrics;
+pub(crate) mod partition;
 pub(crate) mod shuffle;
@@ -0,0 +1,36 @@
+use crate::options::Partition;
+use crate::types::TestDescAndFn;
+
+/// Keeps only the tests in `partition`, preserving their order.
+pub(crate) fn partition_tests(partition: Partition, tests: &mut Vec<TestDescAndFn>) {
+    match partition {
+        Partition::Hash { index, total } => tests
+            .retain(|test| stable_hash(test.desc.name.as_slice()) % total as u64 == index as u64),
+        Partition::Count { index, total } => {
+            let mut by_name: Vec<usize> = (0..tests.len()).collect();
+            by_name.sort_by_key(|&i| tests[i].desc.name.as_slice());
+
+            // The slices differ in length by at most one test.
+            let start = tests.len() * index / total;
+            let end = tests.len() * (index + 1) / total;
+            let mut keep = vec![false; tests.len()];
+            for &i in &by_name[start..end] {
+                keep[i] = true;
+            }
+            let mut keep = keep.into_iter();
+            tests.retain(|_| keep.next().unwrap());
+        }
+    }
+}
+
+/// 64-bit FNV-1a. Unlike `DefaultHasher`, its output is guaranteed not to
+/// change between Rust releases, so a test stays in the same partition no
+/// matter which toolchain built the test binary.
+fn stable_hash(name: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for &byte in name.as_bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
@@ -35,7 +35,7 @@ pub use cli::TestOpts;
 pub use self::ColorConfig::*;
 pub use self::bench::{Bencher, black_box};
 pub use self::console::run_tests_console;
-pub use self::options::{ColorConfig, Options, OutputFormat, RunIgnored, ShouldPanic};
+pub use self::options::{ColorConfig, Options, OutputFormat, Partition, RunIgnored, ShouldPanic};
 pub use self::types::TestName::*;
 pub use self::types::*;
 
@@ -59,7 +59,7 @@ use std::io::prelude::Write;
 use std::mem::ManuallyDrop;
 use std::panic::{self, AssertUnwindSafe, PanicHookInfo, catch_unwind};
 use std::process::{self, Command, Termination};
-use std::sync::mpsc::{Sender, channel};
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender, channel};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use std::{env, io, thread};
@@ -84,6 +84,7 @@ use core::any::Any;
 
 use event::{CompletedTest, TestEvent};
 use helpers::concurrency::get_concurrency;
+use helpers::partition::partition_tests;
 use helpers::shuffle::{get_shuffle_seed, shuffle_tests};
 use options::RunStrategy;
 use test_result::*;
@@ -240,6 +241,19 @@ fn make_owned_test(test: &&TestDescAndFn) -> TestDescAndFn {
     }
 }
 
+/// Copies a test so that it can be run again. Only static tests can be copied.
+fn clone_static_test(test: &TestDescAndFn) -> Option<TestDescAndFn> {
+    let testfn = match test.testfn {
+        StaticTestFn(f) => StaticTestFn(f),
+        StaticBenchAsTestFn(f) => StaticBenchAsTestFn(f),
+        _ => return None,
+    };
+    Some(TestDescAndFn {
+        desc: test.desc.clone(),
+        testfn,
+    })
+}
+
 /// Invoked when unit tests terminate. Returns `Result::Err` if the test is
 /// considered a failure. By default, invokes `report()` and checks for a `0`
 /// result.
@@ -287,7 +301,6 @@ where
 {
     use std::collections::HashMap;
     use std::hash::{BuildHasherDefault, DefaultHasher};
-    use std::sync::mpsc::RecvTimeoutError;
 
     struct RunningTest {
         join_handle: Option<thread::JoinHandle<()>>,
@@ -315,6 +328,12 @@ where
         timeout: Instant,
     }
 
+    // Tests that can be run again if they fail, with how often they have run
+    type RetryMap = HashMap<TestId, (TestDescAndFn, usize), BuildHasherDefault<DefaultHasher>>;
+
+    // The failed runs of tests, by the id of their queued retry
+    type FailedRunMap = HashMap<TestId, CompletedTest, BuildHasherDefault<DefaultHasher>>;
+
     let tests_len = tests.len();
 
     let mut filtered = FilteredTests {
@@ -342,6 +361,23 @@ where
         };
     }
 
+    // Only static tests can be copied to run them again, see
+    // `clone_static_test`.
+    let not_retried = filtered
+        .tests
+        .iter()
+        .filter(|(_, test)| {
+            opts.retries_for(&test.desc) > 0
+                && !matches!(test.testfn, StaticTestFn(_) | StaticBenchAsTestFn(_))
+        })
+        .count();
+    if not_retried > 0 {
+        eprintln!(
+            "warning: {not_retried} test(s) will not be retried on failure, \
+             because only static tests can be run again"
+        );
+    }
+
     let filtered_out = tests_len - filtered.total_len();
     let event = TestEvent::TeFilteredOut(filtered_out);
     notify_about_test_event(event)?;
@@ -369,8 +405,82 @@ where
         RunStrategy::InProcess
     };
 
+    // Tests run in a child process are killed by `spawn_test_subprocess` when
+    // they time out. In-process tests can't be stopped, so they are abandoned
+    // here instead: their thread keeps running, but the run moves on.
+    let in_process_timeout = |desc: &TestDesc| match run_strategy {
+        RunStrategy::InProcess => opts.test_timeout_for(desc),
+        RunStrategy::SpawnPrimary => None,
+    };
+    let mut abandoned_tests = false;
+
     let mut running_tests: TestMap = HashMap::default();
     let mut timeout_queue: VecDeque<TimeoutEntry> = VecDeque::new();
+    let mut deadline_queue: VecDeque<TimeoutEntry> = VecDeque::new();
+    let mut retry_tests: RetryMap = HashMap::default();
+    let mut failed_runs: FailedRunMap = HashMap::default();
+    // Once this many tests have failed, no more tests are started.
+    let max_failures = if opts.fail_fast {
+        Some(1)
+    } else {
+        opts.max_failures
+    };
+    let mut failures = 0;
+    let mut aborted = false;
+    // Every run of a test gets its own id, so that the late result of an
+    // abandoned run can't be mistaken for the result of its retry.
+    let mut next_id = filtered.next_id;
+
+    // Notifies about a test that is started for the first time, and
+    // remembers how to run it again if it may be retried.
+    fn start_test<F>(
+        opts: &TestOpts,
+        id: TestId,
+        test: &TestDescAndFn,
+        retry_tests: &mut RetryMap,
+        notify_about_test_event: &mut F,
+    ) -> io::Result<()>
+    where
+        F: FnMut(TestEvent) -> io::Result<()>,
+    {
+        if retry_tests.contains_key(&id) {
+            // A retry, which is reported as part of the first run.
+            return Ok(());
+        }
+        let copy = (opts.retries_for(&test.desc) > 0)
+            .then(|| clone_static_test(test))
+            .flatten();
+        if let Some(copy) = copy {
+            retry_tests.insert(id, (copy, 1));
+        }
+        let event = TestEvent::TeWait(test.desc.clone());
+        notify_about_test_event(event)
+    }
+
+    // Returns a new run of the test if it failed and has retries left. If it
+    // passed after failing before, marks it as flaky.
+    fn get_retry(
+        opts: &TestOpts,
+        completed_test: &mut CompletedTest,
+        retry_tests: &mut RetryMap,
+        next_id: &mut usize,
+    ) -> Option<(TestId, TestDescAndFn)> {
+        let (test, runs) = retry_tests.remove(&completed_test.id)?;
+        match completed_test.result {
+            ref result if result.is_failure() && runs <= opts.retries_for(&test.desc) => {
+                let id = TestId(*next_id);
+                *next_id += 1;
+                let retry = clone_static_test(&test).unwrap();
+                retry_tests.insert(id, (test, runs + 1));
+                Some((id, retry))
+            }
+            TrOk if runs > 1 => {
+                completed_test.result = TrFlaky(runs);
+                None
+            }
+            _ => None,
+        }
+    }
 
     fn get_timed_out_tests(
         running_tests: &TestMap,
@@ -390,6 +500,23 @@ where
         timed_out
     }
 
+    fn get_abandoned_test(
+        running_tests: &TestMap,
+        deadline_queue: &mut VecDeque<TimeoutEntry>,
+    ) -> Option<TimeoutEntry> {
+        let now = Instant::now();
+        while let Some(deadline_entry) = deadline_queue.front() {
+            if now < deadline_entry.timeout {
+                break;
+            }
+            let deadline_entry = deadline_queue.pop_front().unwrap();
+            if running_tests.contains_key(&deadline_entry.id) {
+                return Some(deadline_entry);
+            }
+        }
+        None
+    }
+
     fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
         timeout_queue.front().map(
             |&TimeoutEntry {
@@ -409,44 +536,96 @@ where
     if concurrency == 1 {
         while !remaining.is_empty() {
             let (id, test) = remaining.pop_front().unwrap();
-            let event = TestEvent::TeWait(test.desc.clone());
-            notify_about_test_event(event)?;
+            failed_runs.remove(&id);
+            let desc = test.desc.clone();
+            start_test(
+                opts,
+                id,
+                &test,
+                &mut retry_tests,
+                &mut notify_about_test_event,
+            )?;
             let join_handle = run_test(opts, !opts.run_tests, id, test, run_strategy, tx.clone());
-            // Wait for the test to complete.
-            let mut completed_test = rx.recv().unwrap();
-            RunningTest { join_handle }.join(&mut completed_test);
-
-            let fail_fast = match completed_test.result {
-                TrIgnored | TrOk | TrBench(_) => false,
-                TrFailed | TrFailedMsg(_) | TrTimedFail => opts.fail_fast,
+            // Wait for the test to complete, or for its deadline if it runs
+            // on a thread that can be abandoned.
+            let deadline = in_process_timeout(&desc)
+                .filter(|_| join_handle.is_some())
+                .map(|timeout| Instant::now() + timeout);
+            let mut completed_test = match recv_result(&rx, id, deadline) {
+                Some(mut completed_test) => {
+                    RunningTest { join_handle }.join(&mut completed_test);
+                    completed_test
+                }
+                None => {
+                    abandoned_tests = true;
+                    CompletedTest::new(id, desc, TrTimedOut, None, Vec::new())
+                }
             };
 
+            if let Some(retry) =
+                get_retry(opts, &mut completed_test, &mut retry_tests, &mut next_id)
+            {
+                failed_runs.insert(retry.0, completed_test);
+                remaining.push_front(retry);
+                continue;
+            }
+
+            failures += completed_test.result.is_failure() as usize;
+
             let event = TestEvent::TeResult(completed_test);
             notify_about_test_event(event)?;
 
-            if fail_fast {
-                return Ok(());
+            if max_failures.is_some_and(|max_failures| failures >= max_failures) {
+                aborted = true;
+                break;
             }
         }
     } else {
-        while pending > 0 || !remaining.is_empty() {
-            while pending < concurrency && !remaining.is_empty() {
+        // After aborting, tests that are already running are still waited for.
+        while pending > 0 || (!aborted && !remaining.is_empty()) {
+            while !aborted && pending < concurrency && !remaining.is_empty() {
                 let (id, test) = remaining.pop_front().unwrap();
+                failed_runs.remove(&id);
                 let timeout = time::get_default_test_timeout();
                 let desc = test.desc.clone();
 
-                let event = TestEvent::TeWait(desc.clone());
-                notify_about_test_event(event)?; //here no pad
+                start_test(
+                    opts,
+                    id,
+                    &test,
+                    &mut retry_tests,
+                    &mut notify_about_test_event,
+                )?;
                 let join_handle =
                     run_test(opts, !opts.run_tests, id, test, run_strategy, tx.clone());
+                if let (Some(test_timeout), Some(_)) = (in_process_timeout(&desc), &join_handle) {
+                    // Tests have timeouts of their own, so deadlines don't
+                    // come in the order the tests are started. Keep the
+                    // earliest one at the front.
+                    let timeout = Instant::now() + test_timeout;
+                    let index = deadline_queue.partition_point(|entry| entry.timeout <= timeout);
+                    deadline_queue.insert(
+                        index,
+                        TimeoutEntry {
+                            id,
+                            desc: desc.clone(),
+                            timeout,
+                        },
+                    );
+                }
                 running_tests.insert(id, RunningTest { join_handle });
                 timeout_queue.push_back(TimeoutEntry { id, desc, timeout });
                 pending += 1;
             }
 
             let mut res;
+            let mut abandon = false;
             loop {
-                if let Some(timeout) = calc_timeout(&timeout_queue) {
+                let timeout = match (calc_timeout(&timeout_queue), calc_timeout(&deadline_queue)) {
+                    (Some(warn), Some(deadline)) => Some(warn.min(deadline)),
+                    (warn, deadline) => warn.or(deadline),
+                };
+                if let Some(timeout) = timeout {
                     res = rx.recv_timeout(timeout);
                     for test in get_timed_out_tests(&running_tests, &mut timeout_queue) {
                         let event = TestEvent::TeTimeout(test);
@@ -455,7 +634,16 @@ where
 
                     match res {
                         Err(RecvTimeoutError::Timeout) => {
-                            // Result is not yet ready, continue waiting.
+                            // Result is not yet ready. Give up on a test that
+                            // is past its deadline, or continue waiting.
+                            if let Some(TimeoutEntry { id, desc, .. }) =
+                                get_abandoned_test(&running_tests, &mut deadline_queue)
+                            {
+                                res =
+                                    Ok(CompletedTest::new(id, desc, TrTimedOut, None, Vec::new()));
+                                abandon = true;
+                                break;
+                            }
                         }
                         _ => {
                             // We've got a result, stop the loop.
@@ -469,43 +657,104 @@ where
             }
 
             let mut completed_test = res.unwrap();
-            let running_test = running_tests.remove(&completed_test.id).unwrap();
-            running_test.join(&mut completed_test);
-
-            let fail_fast = match completed_test.result {
-                TrIgnored | TrOk | TrBench(_) => false,
-                TrFailed | TrFailedMsg(_) | TrTimedFail => opts.fail_fast,
+            let Some(running_test) = running_tests.remove(&completed_test.id) else {
+                // The late result of a test that was abandoned.
+                continue;
             };
+            if abandon {
+                // Detach the thread instead of waiting for it.
+                abandoned_tests = true;
+                drop(running_test);
+            } else {
+                running_test.join(&mut completed_test);
+            }
+            pending -= 1;
+
+            // Failures of tests that were still running when the run was
+            // aborted are final, since no more tests are started.
+            let retry = get_retry(opts, &mut completed_test, &mut retry_tests, &mut next_id);
+            if let Some(retry) = retry.filter(|_| !aborted) {
+                failed_runs.insert(retry.0, completed_test);
+                remaining.push_front(retry);
+                continue;
+            }
+
+            failures += completed_test.result.is_failure() as usize;
 
             let event = TestEvent::TeResult(completed_test);
             notify_about_test_event(event)?;
-            pending -= 1;
 
-            if fail_fast {
-                // Prevent remaining test threads from panicking
-                std::mem::forget(rx);
-                return Ok(());
+            if max_failures.is_some_and(|max_failures| failures >= max_failures) {
+                aborted = true;
             }
         }
     }
 
-    if opts.bench_benchmarks {
+    if aborted {
+        let event = TestEvent::TeAborted;
+        notify_about_test_event(event)?;
+
+        // Report the tests that were never started. A retry that never
+        // started leaves the test with the result of its failed run.
+        let benches = if opts.bench_benchmarks {
+            filtered.benches
+        } else {
+            Vec::new()
+        };
+        for (id, test) in remaining.into_iter().chain(benches) {
+            let completed_test = match failed_runs.remove(&id) {
+                Some(failed_run) => failed_run,
+                None => CompletedTest::new(id, test.desc, TrNotRun, None, Vec::new()),
+            };
+            let event = TestEvent::TeResult(completed_test);
+            notify_about_test_event(event)?;
+        }
+    } else if opts.bench_benchmarks {
         // All benchmarks run at the end, in serial.
         for (id, b) in filtered.benches {
             let event = TestEvent::TeWait(b.desc.clone());
             notify_about_test_event(event)?;
             let join_handle = run_test(opts, false, id, b, run_strategy, tx.clone());
             // Wait for the test to complete.
-            let mut completed_test = rx.recv().unwrap();
+            let mut completed_test = recv_result(&rx, id, None).unwrap();
             RunningTest { join_handle }.join(&mut completed_test);
 
             let event = TestEvent::TeResult(completed_test);
             notify_about_test_event(event)?;
         }
     }
+
+    if abandoned_tests {
+        // Prevent abandoned test threads from panicking
+        std::mem::forget(rx);
+    }
     Ok(())
 }
 
+/// Waits for the result of the test `id`, skipping the late results of tests
+/// that were abandoned after timing out. Returns `None` if `deadline` passes
+/// first.
+fn recv_result(
+    rx: &Receiver<CompletedTest>,
+    id: TestId,
+    deadline: Option<Instant>,
+) -> Option<CompletedTest> {
+    loop {
+        let completed_test = match deadline {
+            Some(deadline) => {
+                match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                    Err(RecvTimeoutError::Timeout) => return None,
+                    res => res.unwrap(),
+                }
+            }
+            None => rx.recv().unwrap(),
+        };
+        if completed_test.id == id {
+            return Some(completed_test);
+        }
+    }
+}
+
 pub fn filter_tests(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> Vec<TestDescAndFn> {
     let mut filtered = tests;
     let matches_filter = |test: &TestDescAndFn, filter: &str| {
@@ -552,6 +801,11 @@ pub fn filter_tests(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> Vec<TestDescA
         RunIgnored::No => {}
     }
 
+    // Keep only this run's share of the tests
+    if let Some(partition) = opts.partition {
+        partition_tests(partition, &mut filtered);
+    }
+
     filtered
 }
 
@@ -606,6 +860,7 @@ pub fn run_test(
             let name = desc.name.clone();
             let nocapture = opts.nocapture;
             let time_options = opts.time_options;
+            let test_timeout = opts.test_timeout_for(&desc);
             let bench_benchmarks = opts.bench_benchmarks;
 
             let runtest = move || match strategy {
@@ -622,9 +877,9 @@ pub fn run_test(
                     id,
                     desc,
                     nocapture,
-                    time_options.is_some(),
                     monitor_ch,
                     time_options,
+                    test_timeout,
                     bench_benchmarks,
                 ),
             };
@@ -732,9 +987,9 @@ fn spawn_test_subprocess(
     id: TestId,
     desc: TestDesc,
     nocapture: bool,
-    report_time: bool,
     monitor_ch: Sender<CompletedTest>,
     time_opts: Option<time::TestTimeOptions>,
+    test_timeout: Option<Duration>,
     bench_benchmarks: bool,
 ) {
     let (result, test_output, exec_time) = (|| {
@@ -751,8 +1006,14 @@ fn spawn_test_subprocess(
             command.stderr(process::Stdio::inherit());
         }
 
-        let start = report_time.then(Instant::now);
-        let output = match command.output() {
+        let start = time_opts.is_some().then(Instant::now);
+        let output = match test_timeout {
+            Some(timeout) => output_with_timeout(&mut command, nocapture, timeout),
+            None => command
+                .output()
+                .map(|output| (Some(output.status), output.stdout, output.stderr)),
+        };
+        let (status, stdout, stderr) = match output {
             Ok(out) => out,
             Err(e) => {
                 let err = format!("Failed to spawn {} as child for test: {:?}", args[0], e);
@@ -764,17 +1025,16 @@ fn spawn_test_subprocess(
             TestExecTime(duration)
         });
 
-        let std::process::Output {
-            stdout,
-            stderr,
-            status,
-        } = output;
         let mut test_output = stdout;
         formatters::write_stderr_delimiter(&mut test_output, &desc.name);
         test_output.extend_from_slice(&stderr);
 
-        let result =
-            get_result_from_exit_code(&desc, status, time_opts.as_ref(), exec_time.as_ref());
+        let result = match status {
+            Some(status) => {
+                get_result_from_exit_code(&desc, status, time_opts.as_ref(), exec_time.as_ref())
+            }
+            None => TrTimedOut,
+        };
         (result, test_output, exec_time)
     })();
 
@@ -782,6 +1042,75 @@ fn spawn_test_subprocess(
     monitor_ch.send(message).unwrap();
 }
 
+/// Like `Command::output`, but kills the child if it is still running after
+/// `timeout`, in which case no exit status is returned.
+fn output_with_timeout(
+    command: &mut Command,
+    nocapture: bool,
+    timeout: Duration,
+) -> io::Result<(Option<process::ExitStatus>, Vec<u8>, Vec<u8>)> {
+    type Capture = (Arc<Mutex<Vec<u8>>>, thread::JoinHandle<()>);
+
+    fn capture(pipe: Option<impl io::Read + Send + 'static>) -> Capture {
+        let buf = Arc::new(Mutex::new(Vec::new()));
+        let reader = thread::spawn({
+            let buf = buf.clone();
+            move || {
+                let Some(mut pipe) = pipe else { return };
+                let mut chunk = [0; 4096];
+                while let Ok(n @ 1..) = pipe.read(&mut chunk) {
+                    buf.lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .extend_from_slice(&chunk[..n]);
+                }
+            }
+        });
+        (buf, reader)
+    }
+
+    fn take((buf, reader): Capture, wait: bool) -> Vec<u8> {
+        if wait {
+            let _ = reader.join();
+        }
+        std::mem::take(&mut *buf.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    // Match the defaults of `Command::output`.
+    command.stdin(process::Stdio::null());
+    if !nocapture {
+        command.stdout(process::Stdio::piped());
+        command.stderr(process::Stdio::piped());
+    }
+    let deadline = Instant::now() + timeout;
+    let mut child = command.spawn()?;
+    // Drain both pipes while waiting so that the child can't block on a full
+    // pipe buffer.
+    let stdout = capture(child.stdout.take());
+    let stderr = capture(child.stderr.take());
+
+    // There is no portable way to wait with a timeout, so poll, backing off
+    // so that quick tests are reaped quickly and slow ones cost little.
+    let mut poll_interval = Duration::from_millis(1);
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            child.kill()?;
+            child.wait()?;
+            break None;
+        }
+        thread::sleep(poll_interval.min(deadline - now));
+        poll_interval = (poll_interval * 2).min(Duration::from_millis(100));
+    };
+
+    // Processes the test spawned may still hold the pipes open after it was
+    // killed, so only wait for the readers if it exited by itself.
+    let finished = status.is_some();
+    Ok((status, take(stdout, finished), take(stderr, finished)))
+}
+
 fn run_test_in_spawned_subprocess(desc: TestDesc, runnable_test: RunnableTest) -> ! {
     let builtin_panic_hook = panic::take_hook();
     let record_result = Arc::new(move |panic_info: Option<&'_ PanicHookInfo<'_>>| {
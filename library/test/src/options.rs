@@ -36,6 +36,8 @@ pub enum OutputFormat {
     Json,
     /// JUnit output
     Junit,
+    /// TAP output
+    Tap,
 }
 
 /// Whether ignored test should be run or not
@@ -47,6 +49,17 @@ pub enum RunIgnored {
     Only,
 }
 
+/// Which share of the tests to run, for splitting a test suite across
+/// several runs. `index` is zero-based and less than `total`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Partition {
+    /// Run the tests whose name hashes to `index` modulo `total`.
+    Hash { index: usize, total: usize },
+    /// Run the `index`th of `total` equally sized slices of the tests, sorted
+    /// by name.
+    Count { index: usize, total: usize },
+}
+
 #[derive(Clone, Copy)]
 pub enum RunStrategy {
     /// Runs the test in the current process, and sends the result back over the
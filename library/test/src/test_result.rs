@@ -35,6 +35,24 @@ pub enum TestResult {
     TrIgnored,
     TrBench(BenchSamples),
     TrTimedFail,
+    /// The test was still running when `--test-timeout` expired.
+    TrTimedOut,
+    /// The test failed, but passed when `--retries` ran it again. Holds the
+    /// number of runs it took, including the passing one.
+    TrFlaky(usize),
+    /// The test was never started, because the run was aborted by
+    /// `--fail-fast` or `--max-failures`.
+    TrNotRun,
+}
+
+impl TestResult {
+    /// Whether the result counts towards `--fail-fast` and `--max-failures`.
+    pub(crate) fn is_failure(&self) -> bool {
+        match self {
+            TrFailed | TrFailedMsg(_) | TrTimedFail | TrTimedOut => true,
+            TrOk | TrIgnored | TrBench(_) | TrFlaky(_) | TrNotRun => false,
+        }
+    }
 }
 
 /// Creates a `TestResult` depending on the raw result of test execution
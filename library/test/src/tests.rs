@@ -1,7 +1,7 @@
 use super::*;
 use crate::{
     console::OutputLocation,
-    formatters::PrettyFormatter,
+    formatters::{JunitFormatter, OutputFormatter, PrettyFormatter, TapFormatter},
     test::{
         MetricMap,
         // FIXME (introduced by #65251)
@@ -31,9 +31,13 @@ impl TestOpts {
             shuffle_seed: None,
             test_threads: None,
             skip: vec![],
+            partition: None,
             time_options: None,
+            test_timeout: None,
+            retries: 0,
             options: Options::new(),
             fail_fast: false,
+            max_failures: None,
         }
     }
 }
@@ -54,6 +58,8 @@ fn one_ignored_one_unignored_test() -> Vec<TestDescAndFn> {
                 compile_fail: false,
                 no_run: false,
                 test_type: TestType::Unknown,
+                timeout: None,
+                retries: None,
             },
             testfn: DynTestFn(Box::new(move || Ok(()))),
         },
@@ -71,6 +77,8 @@ fn one_ignored_one_unignored_test() -> Vec<TestDescAndFn> {
                 compile_fail: false,
                 no_run: false,
                 test_type: TestType::Unknown,
+                timeout: None,
+                retries: None,
             },
             testfn: DynTestFn(Box::new(move || Ok(()))),
         },
@@ -96,6 +104,8 @@ fn do_not_run_ignored_tests() {
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
+            timeout: None,
+            retries: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -131,6 +141,8 @@ fn ignored_tests_result_in_ignored() {
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
+            timeout: None,
+            retries: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -167,6 +179,8 @@ fn test_should_panic() {
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
+            timeout: None,
+            retries: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -203,6 +217,8 @@ fn test_should_panic_good_message() {
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
+            timeout: None,
+            retries: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -244,6 +260,8 @@ fn test_should_panic_bad_message() {
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
+            timeout: None,
+            retries: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -290,6 +308,8 @@ fn test_should_panic_non_string_message_type() {
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
+            timeout: None,
+            retries: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -332,6 +352,8 @@ fn test_should_panic_but_succeeds() {
                 compile_fail: false,
                 no_run: false,
                 test_type: TestType::Unknown,
+                timeout: None,
+                retries: None,
             },
             testfn: DynTestFn(Box::new(f)),
         };
@@ -372,6 +394,8 @@ fn report_time_test_template(report_time: bool) -> Option<TestExecTime> {
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
+            timeout: None,
+            retries: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -428,6 +452,8 @@ fn time_test_failure_template(test_type: TestType) -> TestResult {
             compile_fail: false,
             no_run: false,
             test_type,
+            timeout: None,
+            retries: None,
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -486,6 +512,8 @@ fn typed_test_desc(test_type: TestType) -> TestDesc {
         compile_fail: false,
         no_run: false,
         test_type,
+        timeout: None,
+        retries: None,
     }
 }
 
@@ -493,6 +521,47 @@ fn test_exec_time(millis: u64) -> TestExecTime {
     TestExecTime(Duration::from_millis(millis))
 }
 
+type NamedTestFn = (&'static str, fn() -> Result<(), String>);
+
+fn static_tests(tests: &[NamedTestFn]) -> Vec<TestDescAndFn> {
+    tests
+        .iter()
+        .map(|&(name, testfn)| TestDescAndFn {
+            desc: TestDesc {
+                name: StaticTestName(name),
+                ..typed_test_desc(TestType::Unknown)
+            },
+            testfn: StaticTestFn(testfn),
+        })
+        .collect()
+}
+
+// Runs the tests and returns all events reported about them.
+fn run_tests_and_collect_events(opts: TestOpts, tests: Vec<TestDescAndFn>) -> Vec<TestEvent> {
+    let mut events = Vec::new();
+    let opts = TestOpts {
+        run_tests: true,
+        ..opts
+    };
+    run_tests(&opts, tests, |event| {
+        events.push(event);
+        Ok(())
+    })
+    .unwrap();
+    events
+}
+
+// The name and result of each test, in the order they were reported.
+fn test_results(events: Vec<TestEvent>) -> Vec<(String, TestResult)> {
+    events
+        .into_iter()
+        .filter_map(|event| match event {
+            TestEvent::TeResult(test) => Some((test.desc.name.to_string(), test.result)),
+            _ => None,
+        })
+        .collect()
+}
+
 #[test]
 fn test_time_options_threshold() {
     let unit = TimeThreshold::new(Duration::from_millis(50), Duration::from_millis(100));
@@ -578,6 +647,125 @@ fn parse_include_ignored_flag() {
     assert_eq!(opts.run_ignored, RunIgnored::Yes);
 }
 
+#[test]
+fn parse_test_timeout_option() {
+    let args = vec![
+        "progname".to_string(),
+        "--test-timeout".to_string(),
+        "30".to_string(),
+        "-Zunstable-options".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.test_timeout, Some(Duration::from_secs(30)));
+
+    let args = vec![
+        "progname".to_string(),
+        "--test-timeout".to_string(),
+        "0".to_string(),
+        "-Zunstable-options".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    let args = vec![
+        "progname".to_string(),
+        "--test-timeout".to_string(),
+        "30".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn parse_retries_option() {
+    let args = vec![
+        "progname".to_string(),
+        "--retries".to_string(),
+        "2".to_string(),
+        "-Zunstable-options".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.retries, 2);
+
+    let args = vec![
+        "progname".to_string(),
+        "--retries".to_string(),
+        "x".to_string(),
+        "-Zunstable-options".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    let args = vec![
+        "progname".to_string(),
+        "--retries".to_string(),
+        "2".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn parse_failure_budget_options() {
+    let args = vec![
+        "progname".to_string(),
+        "--fail-fast".to_string(),
+        "-Zunstable-options".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert!(opts.fail_fast);
+    assert_eq!(opts.max_failures, None);
+
+    let args = vec![
+        "progname".to_string(),
+        "--max-failures".to_string(),
+        "3".to_string(),
+        "-Zunstable-options".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert!(!opts.fail_fast);
+    assert_eq!(opts.max_failures, Some(3));
+
+    let args = vec![
+        "progname".to_string(),
+        "--max-failures".to_string(),
+        "0".to_string(),
+        "-Zunstable-options".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    let args = vec!["progname".to_string(), "--fail-fast".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn parse_partition_option() {
+    fn parse(partition: &str) -> Result<Option<Partition>, String> {
+        let args = vec![
+            "progname".to_string(),
+            "--partition".to_string(),
+            partition.to_string(),
+            "-Zunstable-options".to_string(),
+        ];
+        parse_opts(&args).unwrap().map(|opts| opts.partition)
+    }
+
+    assert_eq!(
+        parse("hash:1/4"),
+        Ok(Some(Partition::Hash { index: 0, total: 4 }))
+    );
+    assert_eq!(
+        parse("count:4/4"),
+        Ok(Some(Partition::Count { index: 3, total: 4 }))
+    );
+    for invalid in [
+        "hash:0/4",
+        "count:5/4",
+        "count:1/0",
+        "hash",
+        "hash:1",
+        "random:1/4",
+    ] {
+        assert!(parse(invalid).is_err(), "{invalid}");
+    }
+}
+
 #[test]
 fn filter_for_ignored_option() {
     // When we run ignored tests the test filter should filter out all the
@@ -633,6 +821,8 @@ fn exclude_should_panic_option() {
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
+            timeout: None,
+            retries: None,
         },
         testfn: DynTestFn(Box::new(move || Ok(()))),
     });
@@ -666,6 +856,8 @@ fn exact_filter_match() {
                     compile_fail: false,
                     no_run: false,
                     test_type: TestType::Unknown,
+                    timeout: None,
+                    retries: None,
                 },
                 testfn: DynTestFn(Box::new(move || Ok(()))),
             })
@@ -768,6 +960,63 @@ fn exact_filter_match() {
     assert_eq!(exact.len(), 2);
 }
 
+#[test]
+fn partition_option() {
+    fn names(tests: &[TestDescAndFn]) -> Vec<String> {
+        tests
+            .iter()
+            .map(|test| test.desc.name.to_string())
+            .collect()
+    }
+
+    for partition in [
+        |index, total| Partition::Hash { index, total },
+        |index, total| Partition::Count { index, total },
+    ] {
+        let mut shares = Vec::new();
+        for index in 0..3 {
+            let opts = TestOpts {
+                partition: Some(partition(index, 3)),
+                ..TestOpts::new()
+            };
+            let share = names(&filter_tests(&opts, sample_tests()));
+            // Tests stay in the order they were passed in.
+            let expected: Vec<_> = names(&sample_tests())
+                .into_iter()
+                .filter(|name| share.contains(name))
+                .collect();
+            assert_eq!(share, expected);
+            shares.push(share);
+        }
+
+        // Every test is in exactly one share.
+        let mut all: Vec<_> = shares.concat();
+        all.sort();
+        let mut expected = names(&sample_tests());
+        expected.sort();
+        assert_eq!(all, expected);
+
+        if let Partition::Count { .. } = partition(0, 3) {
+            assert_eq!(shares.iter().map(Vec::len).collect::<Vec<_>>(), [3, 4, 4]);
+            assert_eq!(
+                shares[0],
+                ["sha1::test", "isize::test_to_str", "isize::test_pow"]
+            );
+        }
+    }
+
+    // The hash must not change between builds, or tests would move between
+    // runners.
+    let opts = TestOpts {
+        partition: Some(Partition::Hash { index: 0, total: 3 }),
+        ..TestOpts::new()
+    };
+    assert_eq!(
+        names(&filter_tests(&opts, sample_tests())),
+        ["isize::test_to_str", "isize::test_pow"]
+    );
+}
+
 fn sample_tests() -> Vec<TestDescAndFn> {
     let names = vec![
         "sha1::test".to_string(),
@@ -801,6 +1050,8 @@ fn sample_tests() -> Vec<TestDescAndFn> {
                 compile_fail: false,
                 no_run: false,
                 test_type: TestType::Unknown,
+                timeout: None,
+                retries: None,
             },
             testfn: DynTestFn(Box::new(testfn)),
         };
@@ -965,6 +1216,8 @@ fn test_bench_no_iter() {
         compile_fail: false,
         no_run: false,
         test_type: TestType::Unknown,
+        timeout: None,
+        retries: None,
     };
 
     crate::bench::benchmark(TestId(0), desc, tx, true, f);
@@ -993,6 +1246,8 @@ fn test_bench_iter() {
         compile_fail: false,
         no_run: false,
         test_type: TestType::Unknown,
+        timeout: None,
+        retries: None,
     };
 
     crate::bench::benchmark(TestId(0), desc, tx, true, f);
@@ -1014,6 +1269,8 @@ fn should_sort_failures_before_printing_them() {
         compile_fail: false,
         no_run: false,
         test_type: TestType::Unknown,
+        timeout: None,
+        retries: None,
     };
 
     let test_b = TestDesc {
@@ -1029,6 +1286,8 @@ fn should_sort_failures_before_printing_them() {
         compile_fail: false,
         no_run: false,
         test_type: TestType::Unknown,
+        timeout: None,
+        retries: None,
     };
 
     let mut out = PrettyFormatter::new(OutputLocation::Raw(Vec::new()), false, 10, false, None);
@@ -1038,6 +1297,9 @@ fn should_sort_failures_before_printing_them() {
         total: 0,
         passed: 0,
         failed: 0,
+        flaky: 0,
+        not_run: 0,
+        aborted: false,
         ignored: 0,
         filtered_out: 0,
         measured: 0,
@@ -1061,6 +1323,71 @@ fn should_sort_failures_before_printing_them() {
     assert!(apos < bpos);
 }
 
+#[test]
+fn tap_output() {
+    fn desc(name: &'static str) -> TestDesc {
+        TestDesc {
+            name: StaticTestName(name),
+            ..typed_test_desc(TestType::Unknown)
+        }
+    }
+
+    let mut out = TapFormatter::new(OutputLocation::Raw(Vec::new()));
+    let st = console::ConsoleTestState::new(&TestOpts::new()).unwrap();
+
+    out.write_run_start(5, None).unwrap();
+    out.write_result(&desc("a"), &TrOk, None, b"hidden", &st)
+        .unwrap();
+    out.write_result(
+        &desc("b#1"),
+        &TrFailedMsg("assertion \"x\" failed".to_string()),
+        Some(&test_exec_time(1500)),
+        b"  indented\nline 2\n",
+        &st,
+    )
+    .unwrap();
+    let ignored = TestDesc {
+        ignore: true,
+        ignore_message: Some("slow"),
+        ..desc("c")
+    };
+    out.write_result(&ignored, &TrIgnored, None, b"", &st)
+        .unwrap();
+    let should_panic = TestDesc {
+        should_panic: ShouldPanic::YesWithMessage("boom"),
+        ..desc("d")
+    };
+    out.write_result(&should_panic, &TrOk, None, b"", &st)
+        .unwrap();
+    out.write_result(&should_panic, &TrFailed, None, b"", &st)
+        .unwrap();
+
+    let s = match out.output_location() {
+        OutputLocation::Raw(m) => String::from_utf8_lossy(m),
+        OutputLocation::Pretty(_) => unreachable!(),
+    };
+    assert_eq!(
+        s,
+        "TAP version 14\n\
+         1..5\n\
+         ok 1 - a\n\
+         not ok 2 - b\\#1\n\
+         \x20 ---\n\
+         \x20 message: \"assertion \\\"x\\\" failed\"\n\
+         \x20 duration_ms: 1500\n\
+         \x20 stdout: |2\n\
+         \x20     indented\n\
+         \x20   line 2\n\
+         \x20 ...\n\
+         ok 3 - c # SKIP slow\n\
+         ok 4 - d\n\
+         not ok 5 - d\n\
+         \x20 ---\n\
+         \x20 message: \"test failed\"\n\
+         \x20 ...\n"
+    );
+}
+
 #[test]
 #[cfg(not(target_os = "emscripten"))]
 fn test_dyn_bench_returning_err_fails_when_run_as_test() {
@@ -1081,6 +1408,8 @@ fn test_dyn_bench_returning_err_fails_when_run_as_test() {
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
+            timeout: None,
+            retries: None,
         },
         testfn: DynBenchFn(Box::new(f)),
     };
@@ -1103,3 +1432,274 @@ fn test_dyn_bench_returning_err_fails_when_run_as_test() {
     let result = rx.recv().unwrap().result;
     assert_eq!(result, TrFailed);
 }
+
+#[test]
+#[cfg(not(target_os = "emscripten"))]
+fn test_timeout_abandons_in_process_test() {
+    fn hang() -> Result<(), String> {
+        thread::sleep(Duration::from_secs(60));
+        Ok(())
+    }
+    fn pass() -> Result<(), String> {
+        Ok(())
+    }
+    let tests = static_tests(&[("hang", hang), ("pass", pass)]);
+    let opts = TestOpts {
+        test_threads: Some(2),
+        test_timeout: Some(Duration::from_secs(1)),
+        ..TestOpts::new()
+    };
+    let mut results = test_results(run_tests_and_collect_events(opts, tests));
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        results,
+        [("hang".to_string(), TrTimedOut), ("pass".to_string(), TrOk)]
+    );
+}
+
+#[test]
+#[cfg(not(target_os = "emscripten"))]
+fn test_desc_timeout_overrides_test_timeout() {
+    fn hang() -> Result<(), String> {
+        thread::sleep(Duration::from_secs(60));
+        Ok(())
+    }
+    fn slow() -> Result<(), String> {
+        thread::sleep(Duration::from_secs(2));
+        Ok(())
+    }
+    let mut tests = static_tests(&[("hang", hang), ("slow", slow)]);
+    tests[1].desc.timeout = Some(Duration::from_secs(30));
+    let opts = TestOpts {
+        test_threads: Some(2),
+        test_timeout: Some(Duration::from_secs(1)),
+        ..TestOpts::new()
+    };
+    let mut results = test_results(run_tests_and_collect_events(opts, tests));
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        results,
+        [("hang".to_string(), TrTimedOut), ("slow".to_string(), TrOk)]
+    );
+}
+
+#[test]
+#[cfg(not(target_os = "emscripten"))]
+fn test_timeout_abandons_tests_in_deadline_order() {
+    fn pass() -> Result<(), String> {
+        Ok(())
+    }
+    fn hang() -> Result<(), String> {
+        thread::sleep(Duration::from_secs(60));
+        Ok(())
+    }
+    // The test with the shorter timeout is started second, so its deadline
+    // is queued behind the longer one.
+    let mut tests = static_tests(&[("long", pass), ("short", hang)]);
+    tests[0].desc.timeout = Some(Duration::from_secs(40));
+    tests[1].desc.timeout = Some(Duration::from_secs(1));
+    let opts = TestOpts {
+        test_threads: Some(2),
+        ..TestOpts::new()
+    };
+    let start = Instant::now();
+    let mut results = test_results(run_tests_and_collect_events(opts, tests));
+    assert!(start.elapsed() < Duration::from_secs(20));
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        results,
+        [
+            ("long".to_string(), TrOk),
+            ("short".to_string(), TrTimedOut)
+        ]
+    );
+}
+
+#[test]
+#[cfg_attr(not(panic = "unwind"), ignore = "test requires unwinding support")]
+fn retries_report_flaky_tests() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static FLAKY_RUNS: AtomicUsize = AtomicUsize::new(0);
+    static FAILING_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    fn flaky() -> Result<(), String> {
+        match FLAKY_RUNS.fetch_add(1, Ordering::Relaxed) {
+            0 => Err("first run fails".to_string()),
+            _ => Ok(()),
+        }
+    }
+    fn failing() -> Result<(), String> {
+        FAILING_RUNS.fetch_add(1, Ordering::Relaxed);
+        Err("always fails".to_string())
+    }
+    fn passing() -> Result<(), String> {
+        Ok(())
+    }
+
+    let tests = static_tests(&[("failing", failing), ("flaky", flaky), ("passing", passing)]);
+    let opts = TestOpts {
+        test_threads: Some(1),
+        retries: 2,
+        ..TestOpts::new()
+    };
+    let events = run_tests_and_collect_events(opts, tests);
+
+    // Retries are reported as part of the test's first run.
+    let started: Vec<_> = events
+        .iter()
+        .filter_map(|event| match event {
+            TestEvent::TeWait(desc) => Some(desc.name.to_string()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(started, ["failing", "flaky", "passing"]);
+    assert_eq!(
+        test_results(events),
+        [
+            ("failing".to_string(), TrFailed),
+            ("flaky".to_string(), TrFlaky(2)),
+            ("passing".to_string(), TrOk),
+        ]
+    );
+    assert_eq!(FLAKY_RUNS.load(Ordering::Relaxed), 2);
+    assert_eq!(FAILING_RUNS.load(Ordering::Relaxed), 3);
+}
+
+#[test]
+#[cfg_attr(not(panic = "unwind"), ignore = "test requires unwinding support")]
+fn test_desc_retries_override_retries() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static DEFAULT_RUNS: AtomicUsize = AtomicUsize::new(0);
+    static MORE_RUNS: AtomicUsize = AtomicUsize::new(0);
+    static NONE_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    fn default() -> Result<(), String> {
+        DEFAULT_RUNS.fetch_add(1, Ordering::Relaxed);
+        Err("always fails".to_string())
+    }
+    fn more() -> Result<(), String> {
+        MORE_RUNS.fetch_add(1, Ordering::Relaxed);
+        Err("always fails".to_string())
+    }
+    fn none() -> Result<(), String> {
+        NONE_RUNS.fetch_add(1, Ordering::Relaxed);
+        Err("always fails".to_string())
+    }
+
+    let mut tests = static_tests(&[("default", default), ("more", more), ("none", none)]);
+    tests[1].desc.retries = Some(3);
+    tests[2].desc.retries = Some(0);
+    let opts = TestOpts {
+        test_threads: Some(1),
+        retries: 1,
+        ..TestOpts::new()
+    };
+    run_tests_and_collect_events(opts, tests);
+
+    assert_eq!(DEFAULT_RUNS.load(Ordering::Relaxed), 2);
+    assert_eq!(MORE_RUNS.load(Ordering::Relaxed), 4);
+    assert_eq!(NONE_RUNS.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn junit_marks_flaky_tests() {
+    let desc = TestDesc {
+        name: StaticTestName("flaky"),
+        ..typed_test_desc(TestType::Unknown)
+    };
+
+    let mut out = JunitFormatter::new(OutputLocation::Raw(Vec::new()));
+    let st = console::ConsoleTestState::new(&TestOpts::new()).unwrap();
+    out.write_result(&desc, &TrFlaky(3), Some(&test_exec_time(1500)), b"", &st)
+        .unwrap();
+    out.write_run_finish(&st).unwrap();
+
+    let s = match out.output_location() {
+        OutputLocation::Raw(m) => String::from_utf8_lossy(m),
+        OutputLocation::Pretty(_) => unreachable!(),
+    };
+    assert!(
+        s.contains(
+            "<testcase classname=\"unknown\" name=\"flaky\" time=\"1.5\">\
+             <properties>\
+             <property name=\"flaky\" value=\"true\"/>\
+             <property name=\"runs\" value=\"3\"/>\
+             </properties>\
+             </testcase>"
+        ),
+        "{s}"
+    );
+}
+
+#[test]
+fn max_failures_reports_remaining_tests_as_not_run() {
+    fn fail() -> Result<(), String> {
+        Err("failed".to_string())
+    }
+    fn pass() -> Result<(), String> {
+        Ok(())
+    }
+
+    let tests = static_tests(&[
+        ("a", fail),
+        ("b", pass),
+        ("c", fail),
+        ("d", fail),
+        ("e", pass),
+    ]);
+    let opts = TestOpts {
+        test_threads: Some(1),
+        max_failures: Some(2),
+        ..TestOpts::new()
+    };
+    let events: Vec<_> = run_tests_and_collect_events(opts, tests)
+        .into_iter()
+        .filter_map(|event| match event {
+            TestEvent::TeResult(test) => Some(Some((test.desc.name.to_string(), test.result))),
+            TestEvent::TeAborted => Some(None),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        events,
+        [
+            Some(("a".to_string(), TrFailed)),
+            Some(("b".to_string(), TrOk)),
+            Some(("c".to_string(), TrFailed)),
+            None,
+            Some(("d".to_string(), TrNotRun)),
+            Some(("e".to_string(), TrNotRun)),
+        ]
+    );
+}
+
+#[test]
+fn summary_reports_aborted_runs() {
+    // The budget can run out on the last test, leaving none that were not run.
+    let st = console::ConsoleTestState {
+        failed: 1,
+        aborted: true,
+        ..console::ConsoleTestState::new(&TestOpts::new()).unwrap()
+    };
+
+    let mut out = PrettyFormatter::new(OutputLocation::Raw(Vec::new()), false, 10, false, None);
+    out.write_run_finish(&st).unwrap();
+    let s = match out.output_location() {
+        OutputLocation::Raw(m) => String::from_utf8_lossy(m),
+        OutputLocation::Pretty(_) => unreachable!(),
+    };
+    assert!(
+        s.contains("test result: FAILED (aborted early). 0 passed; 1 failed;"),
+        "{s}"
+    );
+
+    let mut out = TapFormatter::new(OutputLocation::Raw(Vec::new()));
+    out.write_run_finish(&st).unwrap();
+    let s = match out.output_location() {
+        OutputLocation::Raw(m) => String::from_utf8_lossy(m),
+        OutputLocation::Pretty(_) => unreachable!(),
+    };
+    assert!(s.ends_with("# the run was aborted early\n"), "{s}");
+}
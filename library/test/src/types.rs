@@ -3,6 +3,7 @@
 use std::borrow::Cow;
 use std::fmt;
 use std::sync::mpsc::Sender;
+use std::time::Duration;
 
 pub use NamePadding::*;
 pub use TestFn::*;
@@ -207,6 +208,12 @@ pub struct TestDesc {
     pub compile_fail: bool,
     pub no_run: bool,
     pub test_type: TestType,
+    /// Fail the test if it is still running after this long, instead of
+    /// after the time given with `--test-timeout`.
+    pub timeout: Option<Duration>,
+    /// Run the test again up to this many times if it fails, instead of as
+    /// often as `--retries` allows.
+    pub retries: Option<usize>,
 }
 
 impl TestDesc {
@@ -279,6 +286,8 @@ impl TestDescAndFn {
                     options::ShouldPanic::No
                 },
                 test_type: TestType::DocTest,
+                timeout: None,
+                retries: None,
             },
             testfn,
         }
@@ -54,7 +54,7 @@ cfg_if::cfg_if! {
     }
 }
 
-#[cfg(target_env = "musl")]
+#[cfg(all(target_env = "musl", not(target_os = "aether")))]
 cfg_if::cfg_if! {
     if #[cfg(all(feature = "llvm-libunwind", feature = "system-llvm-libunwind"))] {
         compile_error!("`llvm-libunwind` and `system-llvm-libunwind` cannot be enabled at the same time");
@@ -109,6 +109,21 @@ cfg_if::cfg_if! {
 #[link(name = "dl", cfg(not(target_feature = "crt-static")))]
 unsafe extern "C" {}
 
+// aether binaries are always linked statically against LLVM libunwind from
+// the libc sysroot. It finds `.eh_frame_hdr` through `dl_iterate_phdr`, which
+// the static libc implements from the program headers of the executable, so
+// libc has to be listed again after the unwinder.
+#[cfg(target_os = "aether")]
+cfg_if::cfg_if! {
+    if #[cfg(all(feature = "llvm-libunwind", feature = "system-llvm-libunwind"))] {
+        compile_error!("`llvm-libunwind` and `system-llvm-libunwind` cannot be enabled at the same time");
+    } else {
+        #[link(name = "unwind", kind = "static", modifiers = "-bundle")]
+        #[link(name = "c", kind = "static", modifiers = "-bundle")]
+        unsafe extern "C" {}
+    }
+}
+
 // When building with crt-static, we get `gcc_eh` from the `libc` crate, since
 // glibc needs it, and needs it listed later on the linker command line. We
 // don't want to duplicate it here.